@@ -32,9 +32,7 @@ impl LayoutItem for ColorBox {
     type Blueprint = Graphic;
 
     fn prepare(&mut self, _: ParentHints) -> ChildHints {
-        ChildHints {
-            minimum_size: self.size,
-        }
+        ChildHints::new(self.size)
     }
 
     fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
@@ -4,7 +4,12 @@ use {
     image::{DynamicImage, GenericImageView},
     std::sync::Arc,
     ui_composer_core::{
-        app::composition::layout::hints::{ChildHints, ParentHints},
+        app::{
+            composition::layout::hints::{ChildHints, ParentHints},
+            runner::headless::{
+                HeadlessBlueprintResources, HeadlessEnvironment,
+            },
+        },
         prelude::{Blueprint, LayoutItem},
     },
     ui_composer_math::prelude::{Rect, Size2},
@@ -44,7 +49,13 @@ impl LayoutItem for ImageView {
             Size2::<f32>::new(w as f32, h as f32)
         });
 
-        ChildHints { minimum_size: size }
+        // Images keep their proportions as they are resized.
+        let hints = ChildHints::new(size);
+        if size.height > 0.0 {
+            hints.with_aspect_ratio(size.width / size.height)
+        } else {
+            hints
+        }
     }
 
     fn place(
@@ -71,3 +82,11 @@ impl Blueprint<TerminalEnvironment> for ImageViewBlueprint {
         ImageViewElementTerminal::new(self.rect, self.image)
     }
 }
+
+impl Blueprint<HeadlessEnvironment> for ImageViewBlueprint {
+    type Element = ImageViewElementTerminal;
+
+    fn make(self, _: &HeadlessBlueprintResources) -> Self::Element {
+        ImageViewElementTerminal::new(self.rect, self.image)
+    }
+}
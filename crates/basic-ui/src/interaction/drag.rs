@@ -1,7 +1,12 @@
 #![allow(unused)]
 
+use ui_composer_derive_ui::{Blueprint, Element};
 use {
-    ui_composer_core::app::composition::algebra::Bubble,
+    ui_composer_core::app::composition::{
+        algebra::Bubble,
+        elements::response::{CursorIcon, EventResponse, PointerCapture},
+        pointer::{CaptureId, Pointers},
+    },
     ui_composer_input::event::CursorEvent,
     ui_composer_math::{
         glamour::Contains,
@@ -23,7 +28,11 @@ pub enum DragState {
 }
 
 /// An Interactor that handles a user dragging the window.
-#[derive(Debug, Clone)]
+///
+/// Once a drag starts, it captures the pointer, so the drag continues
+/// even if the cursor leaves the rect, until the button is released.
+#[derive(Debug, Clone, Blueprint, Element)]
+#[element(effect = ())]
 pub struct Drag {
     rect: Rect,
     drag_state: Mutable<DragState>,
@@ -31,6 +40,9 @@ pub struct Drag {
 
     // The thing we actually care about...
     displacement: Mutable<Vector2>,
+
+    pointers: Option<Pointers>,
+    capture_id: CaptureId,
 }
 
 impl Drag {
@@ -46,71 +58,98 @@ impl Drag {
             mouse_position,
 
             displacement,
+
+            pointers: None,
+            capture_id: CaptureId::new(),
+        }
+    }
+
+    /// Shares pointer state with other interactors, so that while this is dragging,
+    /// they ignore the pointer, and this ignores pointers they captured.
+    pub fn with_pointers(self, pointers: Pointers) -> Self {
+        Self {
+            pointers: Some(pointers),
+            ..self
         }
     }
 }
 
-impl Bubble<Event, bool> for Drag {
-    fn bubble(&mut self, event: &mut Event) -> bool {
+impl Bubble<Event, EventResponse> for Drag {
+    fn bubble(&mut self, event: &mut Event) -> EventResponse {
         if let Event::Cursor { id, event } = event {
+            if let Some(pointers) = &self.pointers
+                && pointers.is_captured_by_other(*id, self.capture_id)
+            {
+                return EventResponse::ignored();
+            }
+
             match (event, self.drag_state.get()) {
                 (CursorEvent::Moved { position }, DragState::None) => {
                     self.mouse_position.set(*position);
                     if self.rect.contains(position) {
                         self.drag_state.set(DragState::Hovering);
+                        return EventResponse::handled()
+                            .with_cursor_icon(CursorIcon::Grab);
                     }
-                    true
+                    EventResponse::handled()
                 }
                 (CursorEvent::Moved { position }, DragState::Hovering) => {
                     self.mouse_position.set(*position);
                     if !self.rect.contains(position) {
                         self.drag_state.set(DragState::None);
+                        return EventResponse::ignored();
                     }
-                    false
+                    EventResponse::ignored().with_cursor_icon(CursorIcon::Grab)
                 }
                 (CursorEvent::Moved { position }, DragState::Dragging) => {
-                    if self.rect.contains(position) {
-                        let delta = *position - self.mouse_position.get();
-                        *self.displacement.lock_mut() += delta;
-                    } else {
-                        // If the mouse leaves the drag area, drag stops.
-                        // Maybe this shouldn't be here.
-                        self.drag_state.set(DragState::None);
-                    }
+                    // The pointer is captured, so the drag goes on outside the rect.
+                    let delta = *position - self.mouse_position.get();
+                    *self.displacement.lock_mut() += delta;
                     self.mouse_position.set(*position);
-                    true
+                    EventResponse::handled()
+                        .with_cursor_icon(CursorIcon::Grabbing)
                 }
-                (
-                    CursorEvent::Exited,
-                    DragState::Dragging | DragState::Hovering,
-                ) => {
+                (CursorEvent::Exited, DragState::Hovering) => {
                     self.drag_state.set(DragState::None);
-                    false
+                    EventResponse::ignored()
                 }
                 (CursorEvent::Button(button, state), DragState::Hovering) => {
                     if let (MouseButton::Left, ButtonState::Pressed) =
                         (button, state)
                     {
                         self.drag_state.set(DragState::Dragging);
-                        true
+                        if let Some(pointers) = &self.pointers {
+                            pointers.capture(*id, self.capture_id);
+                        }
+                        EventResponse::handled()
+                            .with_pointer_capture(PointerCapture::Capture(*id))
                     } else {
-                        false
+                        EventResponse::ignored()
                     }
                 }
                 (CursorEvent::Button(button, state), DragState::Dragging) => {
                     if let (MouseButton::Left, ButtonState::Released) =
                         (button, state)
                     {
-                        self.drag_state.set(DragState::Hovering);
-                        true
+                        let position = self.mouse_position.get();
+                        self.drag_state.set(if self.rect.contains(&position) {
+                            DragState::Hovering
+                        } else {
+                            DragState::None
+                        });
+                        if let Some(pointers) = &self.pointers {
+                            pointers.release(*id, self.capture_id);
+                        }
+                        EventResponse::handled()
+                            .with_pointer_capture(PointerCapture::Release(*id))
                     } else {
-                        false
+                        EventResponse::ignored()
                     }
                 }
-                _ => false,
+                _ => EventResponse::ignored(),
             }
         } else {
-            false
+            EventResponse::ignored()
         }
     }
 }
@@ -1,7 +1,12 @@
 #![allow(unused)]
 
+use ui_composer_derive_ui::{Blueprint, Element};
 use {
-    ui_composer_core::app::composition::algebra::Bubble,
+    ui_composer_core::app::composition::{
+        algebra::Bubble,
+        elements::response::EventResponse,
+        pointer::{CaptureId, Pointers},
+    },
     ui_composer_input::event::{CursorEvent, Event},
     ui_composer_math::glamour::Contains,
 };
@@ -11,9 +16,13 @@ use {
 };
 
 /// An Interactor that handles a user hovering over it with a cursor.
+#[derive(Blueprint, Element)]
+#[element(effect = ())]
 pub struct Hover {
     rect: Rect,
     is_hovered_state: Mutable<bool>,
+    pointers: Option<Pointers>,
+    capture_id: CaptureId,
 }
 
 impl Hover {
@@ -21,27 +30,45 @@ impl Hover {
         Self {
             rect,
             is_hovered_state,
+            pointers: None,
+            capture_id: CaptureId::new(),
+        }
+    }
+
+    /// Ignores pointers other interactors captured in a shared context.
+    pub fn with_pointers(self, pointers: Pointers) -> Self {
+        Self {
+            pointers: Some(pointers),
+            ..self
         }
     }
 }
 
-impl Bubble<Event, bool> for Hover {
-    fn bubble(&mut self, event: &mut Event) -> bool {
+impl Bubble<Event, EventResponse> for Hover {
+    fn bubble(&mut self, event: &mut Event) -> EventResponse {
         match event {
+            Event::Cursor { id, .. }
+                if self.pointers.as_ref().is_some_and(|pointers| {
+                    pointers.is_captured_by_other(*id, self.capture_id)
+                }) =>
+            {
+                self.is_hovered_state.set_neq(false);
+                EventResponse::ignored()
+            }
             Event::Cursor { id, event } => match event {
                 CursorEvent::Moved { position } => {
                     let rect_contains_point = self.rect.contains(position);
                     self.is_hovered_state
                         .set_if(rect_contains_point, |a, b| a != b);
-                    true
+                    EventResponse::handled()
                 }
                 CursorEvent::Exited => {
                     self.is_hovered_state.set(false);
-                    false
+                    EventResponse::ignored()
                 }
-                _ => false,
+                _ => EventResponse::ignored(),
             },
-            _ => false,
+            _ => EventResponse::ignored(),
         }
     }
 }
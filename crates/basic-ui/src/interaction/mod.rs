@@ -7,5 +7,3 @@ pub use drag::*;
 pub use hover::*;
 pub use tap::*;
 pub use typing::*;
-
-pub mod implementations;
@@ -1,7 +1,16 @@
 #![allow(unused)]
+use std::pin::Pin;
 use ui_composer_core::app::composition::algebra::Bubble;
+use ui_composer_core::app::composition::elements::response::{
+    CursorIcon, EventResponse,
+};
 use ui_composer_core::app::composition::elements::{Blueprint, Element};
-use ui_composer_input::event::{ButtonState, MouseButton};
+use ui_composer_core::app::composition::focus::Focusable;
+use ui_composer_core::app::composition::pointer::{CaptureId, Pointers};
+use ui_composer_derive_ui::{Blueprint, Element};
+use ui_composer_input::event::{
+    ButtonState, KeyEvent, KeyboardEvent, MouseButton, NamedKey,
+};
 use ui_composer_input::event::{CursorEvent, Event, TouchStage};
 use ui_composer_math::glamour::Contains;
 use ui_composer_math::prelude::{Point2, Rect};
@@ -9,15 +18,27 @@ use ui_composer_state::effect::Effect;
 use ui_composer_state::futures_signals::signal::Mutable;
 
 /// An Interactor that handles a user hovering over it with a cursor.
+#[derive(Blueprint, Element)]
+#[element(
+    effect = (),
+    reconcile = Self::patch,
+    bound = "A: Effect + Send + Sync + 'static"
+)]
 pub struct Tap<A: Effect> {
     pub rect: Rect,
     pub tap_effect: A,
     is_hovered_state: Mutable<bool>,
-    /// Todo: this shouldn't be saved per `Tap`,
-    /// instead, it should be provided to us in a cascading context.
+    focus: Option<Focusable>,
+    pointers: Option<Pointers>,
+    capture_id: CaptureId,
+    /// Where the cursor was last seen,
+    /// used when no [`Pointers`] context was given.
     mouse_position_state: Option<Point2>,
 }
 
+// A `Tap` never pins its fields, so it can be patched through a pin.
+impl<A: Effect> Unpin for Tap<A> {}
+
 impl<Fx> Tap<Fx>
 where
     Fx: Effect,
@@ -28,6 +49,9 @@ where
             mouse_position_state: None,
             tap_effect,
             is_hovered_state: Mutable::new(false),
+            focus: None,
+            pointers: None,
+            capture_id: CaptureId::new(),
         }
     }
 
@@ -37,23 +61,80 @@ where
             ..self
         }
     }
+
+    /// Lets this `Tap` be focused, by tapping it or through its focus scope.
+    /// While focused, pressing `Enter` or `Space` taps it.
+    pub fn with_focus(self, focus: Focusable) -> Self {
+        focus.set_rect(self.rect);
+        Self {
+            focus: Some(focus),
+            ..self
+        }
+    }
+
+    /// Reads where pointers are from a shared context, instead of remembering it,
+    /// and ignores pointers other interactors captured.
+    pub fn with_pointers(self, pointers: Pointers) -> Self {
+        Self {
+            pointers: Some(pointers),
+            ..self
+        }
+    }
+
+    /// Updates an existing `Tap` to match this one,
+    /// but keeps track of where the cursor was.
+    pub(crate) fn patch(self, element: Pin<&mut Self>) {
+        let element = element.get_mut();
+        element.rect = self.rect;
+        element.tap_effect = self.tap_effect;
+        element.is_hovered_state = self.is_hovered_state;
+        element.focus = self.focus;
+        element.pointers = self.pointers;
+
+        let is_hovered = match &element.pointers {
+            Some(pointers) => {
+                Some(pointers.any_within(element.rect, element.capture_id))
+            }
+            None => element
+                .mouse_position_state
+                .map(|position| element.rect.contains(&position)),
+        };
+        if let Some(is_hovered) = is_hovered {
+            element.is_hovered_state.set_neq(is_hovered);
+        }
+    }
 }
 
-impl<A> Bubble<Event, bool> for Tap<A>
+impl<A> Bubble<Event, EventResponse> for Tap<A>
 where
     A: Effect + Send + Sync,
 {
-    fn bubble(&mut self, event: &mut Event) -> bool {
+    fn bubble(&mut self, event: &mut Event) -> EventResponse {
         match event {
+            Event::Cursor { id, .. }
+                if self.pointers.as_ref().is_some_and(|pointers| {
+                    pointers.is_captured_by_other(*id, self.capture_id)
+                }) =>
+            {
+                self.is_hovered_state.set_neq(false);
+                EventResponse::ignored()
+            }
             Event::Cursor { id: _, event } => match event {
                 CursorEvent::Moved { position } => {
                     self.mouse_position_state = Some(*position);
-                    self.is_hovered_state.set(self.rect.contains(position));
-                    false
+                    let is_hovered = self.rect.contains(position);
+                    self.is_hovered_state.set(is_hovered);
+
+                    if is_hovered {
+                        EventResponse::ignored()
+                            .with_cursor_icon(CursorIcon::Pointer)
+                    } else {
+                        EventResponse::ignored()
+                    }
                 }
                 CursorEvent::Exited => {
                     self.is_hovered_state.set(false);
-                    false
+                    EventResponse::ignored()
                 }
                 CursorEvent::Button(
                     MouseButton::Left,
@@ -64,11 +145,27 @@ where
                     ..
                 } if self.is_hovered_state.get() => {
                     self.tap_effect.apply();
-                    true
+                    match &self.focus {
+                        Some(focus) => EventResponse::handled()
+                            .with_focus_request(focus.id()),
+                        None => EventResponse::handled(),
+                    }
                 }
-                _ => false,
+                _ => EventResponse::ignored(),
             },
-            _ => false,
+            Event::Keyboard {
+                event:
+                    KeyboardEvent::Key(KeyEvent {
+                        named_key: Some(NamedKey::Enter | NamedKey::Space),
+                        button_state: ButtonState::Pressed,
+                        ..
+                    }),
+                ..
+            } if self.focus.as_ref().is_some_and(Focusable::is_focused) => {
+                self.tap_effect.apply();
+                EventResponse::handled()
+            }
+            _ => EventResponse::ignored(),
         }
     }
 }
@@ -1,40 +1,97 @@
 use ui_composer_core::app::composition::algebra::Bubble;
+use ui_composer_core::app::composition::elements::response::EventResponse;
+use ui_composer_core::app::composition::focus::Focusable;
+use ui_composer_derive_ui::{Blueprint, Element};
 use ui_composer_input::event::Event;
-use ui_composer_input::event::{ButtonState, KeyEvent, KeyboardEvent};
+use ui_composer_input::event::{
+    ButtonState, KeyEvent, KeyboardEvent, NamedKey,
+};
+use ui_composer_math::prelude::Rect;
 use ui_composer_state::futures_signals::signal::Mutable;
 
 /// Input item that receives key events...
-#[derive(Clone)]
+///
+/// Unless it is given a [`Focusable`] with [`Typing::with_focus`],
+/// it receives every key pressed anywhere in the app.
+#[derive(Clone, Blueprint, Element)]
+#[element(effect = ())]
 pub struct Typing {
     state: Mutable<String>,
+    focus: Option<Focusable>,
+    rect: Option<Rect>,
 }
 
 impl Typing {
     pub fn new(state: Mutable<String>) -> Self {
-        Self { state }
+        Self {
+            state,
+            focus: None,
+            rect: None,
+        }
+    }
+
+    /// Makes this item only receive keys while `focus` is focused.
+    pub fn with_focus(self, focus: Focusable) -> Self {
+        if let Some(rect) = self.rect {
+            focus.set_rect(rect);
+        }
+        Self {
+            focus: Some(focus),
+            ..self
+        }
+    }
+
+    /// Tells where this item is, so that its focus scope
+    /// can put it in the right place in focus order.
+    pub fn with_rect(self, rect: Rect) -> Self {
+        if let Some(focus) = &self.focus {
+            focus.set_rect(rect);
+        }
+        Self {
+            rect: Some(rect),
+            ..self
+        }
     }
 }
 
-impl Bubble<Event, bool> for Typing {
-    fn bubble(&mut self, event: &mut Event) -> bool {
+impl Bubble<Event, EventResponse> for Typing {
+    fn bubble(&mut self, event: &mut Event) -> EventResponse {
+        if let Some(focus) = &self.focus
+            && !focus.is_focused()
+        {
+            return EventResponse::ignored();
+        }
+
         if let Event::Keyboard {
             event:
                 KeyboardEvent::Key(KeyEvent {
-                    text_repr: Some(text),
+                    text_repr,
+                    named_key,
                     button_state: ButtonState::Pressed,
                     ..
                 }),
             ..
         } = event
         {
-            if text == "\u{08}" {
-                self.state.lock_mut().pop();
-            } else {
-                self.state.lock_mut().push_str(text.as_str());
+            match (named_key, text_repr) {
+                // Tab is left for the focus scope, so focus can leave this item.
+                (Some(NamedKey::Tab), _) if self.focus.is_some() => {}
+                (Some(NamedKey::Backspace), _) => {
+                    self.state.lock_mut().pop();
+                    return EventResponse::handled();
+                }
+                (_, Some(text)) => {
+                    if text == "\u{08}" {
+                        self.state.lock_mut().pop();
+                    } else {
+                        self.state.lock_mut().push_str(text.as_str());
+                    }
+                    return EventResponse::handled();
+                }
+                _ => {}
             }
-            return true;
         }
 
-        false
+        EventResponse::ignored()
     }
 }
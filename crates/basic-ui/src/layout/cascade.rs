@@ -0,0 +1,66 @@
+use ui_composer_core::app::composition::layout::{
+    LayoutItem,
+    hints::{CascadingContext, ChildHints, ParentHints},
+};
+
+/// A container that overrides the [`CascadingContext`] its item (and everything inside it) sees.
+///
+/// ```rust,ignore
+/// cascade(|cx| cx.with_text_color(ACCENT), Label("Hello!"))
+/// ```
+pub fn cascade<A, F>(modify: F, item: A) -> CascadeContainer<A, F>
+where
+    A: LayoutItem,
+    F: FnMut(CascadingContext) -> CascadingContext + Send,
+{
+    CascadeContainer { modify, item }
+}
+
+pub struct CascadeContainer<A, F>
+where
+    A: LayoutItem,
+    F: FnMut(CascadingContext) -> CascadingContext + Send,
+{
+    modify: F,
+    item: A,
+}
+
+impl<A, F> CascadeContainer<A, F>
+where
+    A: LayoutItem,
+    F: FnMut(CascadingContext) -> CascadingContext + Send,
+{
+    fn inner_hints(&mut self, parent_hints: ParentHints) -> ParentHints {
+        ParentHints {
+            context: (self.modify)(parent_hints.context),
+            ..parent_hints
+        }
+    }
+}
+
+impl<A, F> LayoutItem for CascadeContainer<A, F>
+where
+    A: LayoutItem,
+    F: FnMut(CascadingContext) -> CascadingContext + Send,
+{
+    type Blueprint = A::Blueprint;
+
+    fn prepare(&mut self, parent_hints: ParentHints) -> ChildHints {
+        let inner_hints = self.inner_hints(parent_hints);
+        self.item.prepare(inner_hints)
+    }
+
+    fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
+        let inner_hints = self.inner_hints(parent_hints);
+        self.item.place(inner_hints)
+    }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        let inner_hints = self.inner_hints(parent_hints);
+        self.item.height_for_width(width, inner_hints)
+    }
+}
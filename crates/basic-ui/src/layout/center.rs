@@ -32,12 +32,20 @@ where
     fn prepare(&mut self, parent_hints: ParentHints) -> ChildHints {
         let hints = self.item.prepare(parent_hints);
         self._item_hints_cache = hints;
-        hints
+
+        // The container itself can grow freely, it's the item that doesn't.
+        ChildHints {
+            maximum_size: None,
+            aspect_ratio: None,
+            ..hints
+        }
     }
 
     fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
         let my_rect = parent_hints.rect;
-        let item_size = self._item_hints_cache.minimum_size;
+        let item_hints = self._item_hints_cache;
+        let item_size =
+            item_hints.fit(item_hints.natural_size().min(my_rect.size));
         let item_position =
             my_rect.origin + (my_rect.size - item_size).to_vector() / 2.0;
 
@@ -50,4 +58,12 @@ where
 
         self.item.place(inner_hints)
     }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        self.item.height_for_width(width, parent_hints)
+    }
 }
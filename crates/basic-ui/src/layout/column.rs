@@ -1,5 +1,6 @@
 use core::f32;
 
+use super::StackItemList;
 use ui_composer_core::app::composition::layout::{
     LayoutItem,
     hints::{ChildHints, ParentHints},
@@ -13,89 +14,136 @@ use ui_composer_math::prelude::{Rect, Size2, Vector2};
 /// of the items inside (accounting for gap).
 ///
 /// The width of the container is the max width between the items.
-/// TODO: Allow to take more than two items.
-pub fn column<A, B>((item_a, item_b): (A, B)) -> ColumnContainer<A, B> {
+pub fn column<Items: StackItemList>(items: Items) -> ColumnContainer<Items> {
     ColumnContainer {
-        item_a,
-        item_b,
+        items,
         gap: 0.0,
-        __item_a_hints_cache: ChildHints::default(),
-        __item_b_hints_cache: ChildHints::default(),
+        __items_hints_cache: Vec::new(),
     }
 }
 
-pub struct ColumnContainer<A, B> {
-    pub item_a: A,
-    pub item_b: B,
+pub struct ColumnContainer<Items> {
+    pub items: Items,
     pub gap: f32,
-    __item_a_hints_cache: ChildHints,
-    __item_b_hints_cache: ChildHints,
+    __items_hints_cache: Vec<ChildHints>,
 }
 
-impl<A, B> ColumnContainer<A, B> {
+impl<Items> ColumnContainer<Items> {
     /// Adds some spacing between elements.
     pub fn with_gap(self, gap: f32) -> Self {
         Self { gap, ..self }
     }
 }
 
-impl<A, B> LayoutItem for ColumnContainer<A, B>
+impl<Items> ColumnContainer<Items>
 where
-    A: LayoutItem,
-    B: LayoutItem,
+    Items: StackItemList,
 {
-    type Blueprint = (A::Blueprint, B::Blueprint);
-
-    fn prepare(
+    /// How tall each item needs to be if the column is `width` wide.
+    fn item_heights(
         &mut self,
+        width: f32,
         parent_hints: ParentHints,
-    ) -> ui_composer_core::app::composition::layout::hints::ChildHints {
-        let inner_hints = ParentHints {
+    ) -> Vec<f32> {
+        let item_hints = ParentHints {
             rect: Rect::new(
                 parent_hints.rect.origin,
-                Size2::new(parent_hints.rect.size.width, f32::INFINITY),
+                Size2::new(width, parent_hints.rect.size.height),
             ),
             ..parent_hints
         };
-        let a = self.item_a.prepare(inner_hints);
-        let b = self.item_b.prepare(inner_hints);
-
-        self.__item_a_hints_cache = a;
-        self.__item_b_hints_cache = b;
-
-        let minimum_size = Size2::new(
-            a.minimum_size.width.max(b.minimum_size.width), // Max width
-            a.minimum_size.height + self.gap + b.minimum_size.height, // Min height with gap
+        let mut heights = Vec::new();
+        self.items.heights_for_width(
+            &mut core::iter::repeat(item_hints),
+            &mut heights,
         );
-        ChildHints { minimum_size }
+        heights
     }
 
-    fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
-        let a = self.item_a.place(ParentHints {
+    fn total_gap(&self, item_count: usize) -> f32 {
+        self.gap * item_count.saturating_sub(1) as f32
+    }
+}
+
+impl<Items> LayoutItem for ColumnContainer<Items>
+where
+    Items: StackItemList + Send,
+{
+    type Blueprint = Items::Content;
+
+    fn prepare(&mut self, parent_hints: ParentHints) -> ChildHints {
+        let inner_hints = ParentHints {
             rect: Rect::new(
                 parent_hints.rect.origin,
-                Size2::new(
-                    parent_hints.rect.size.width,
-                    self.__item_a_hints_cache.minimum_size.height,
-                ),
+                Size2::new(parent_hints.rect.size.width, f32::INFINITY),
             ),
             ..parent_hints
-        });
+        };
+        let mut hints = Vec::new();
+        self.items.prepare(inner_hints.clone(), &mut hints);
 
-        let b = self.item_b.place(ParentHints {
-            rect: Rect::new(
-                parent_hints.rect.origin.translate(Vector2::new(
-                    0.0,
-                    self.__item_a_hints_cache.minimum_size.height + self.gap,
-                )),
-                Size2::new(
-                    parent_hints.rect.size.width,
-                    self.__item_b_hints_cache.minimum_size.height,
-                ),
-            ),
-            ..parent_hints
-        });
+        // Items get the whole width, so wrapping ones can tell how tall they'll be.
+        let heights =
+            self.item_heights(parent_hints.rect.size.width, inner_hints);
+        for (hints, height) in hints.iter_mut().zip(heights) {
+            *hints = hints.with_measured_height(height);
+        }
+
+        let total_gap = self.total_gap(hints.len());
+        let mut minimum_size = Size2::<f32>::new(0.0, total_gap);
+        let mut preferred_size = Size2::<f32>::new(0.0, total_gap);
+        for hints in hints.iter() {
+            let natural_size = hints.natural_size();
+            minimum_size.width =
+                minimum_size.width.max(hints.minimum_size.width);
+            minimum_size.height += hints.minimum_size.height;
+            preferred_size.width = preferred_size.width.max(natural_size.width);
+            preferred_size.height += natural_size.height;
+        }
+
+        self.__items_hints_cache = hints;
+        ChildHints::new(minimum_size).with_preferred_size(preferred_size)
+    }
 
-        (a, b)
+    fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
+        let width = parent_hints.rect.size.width;
+        let heights = self.item_heights(width, parent_hints.clone());
+
+        // Items get their natural height, and as much width as they'll take.
+        let mut offset = 0.0;
+        let rects = self
+            .__items_hints_cache
+            .iter()
+            .zip(heights)
+            .map(|(hints, height)| {
+                let hints = hints.with_measured_height(height);
+                let size =
+                    hints.fit(Size2::new(width, hints.natural_size().height));
+                let rect = Rect::new(
+                    parent_hints
+                        .rect
+                        .origin
+                        .translate(Vector2::new(0.0, offset)),
+                    size,
+                );
+                offset += size.height + self.gap;
+                rect
+            })
+            .collect::<Vec<_>>();
+
+        self.items
+            .place(&mut rects.into_iter().map(|rect| ParentHints {
+                rect,
+                ..parent_hints.clone()
+            }))
+    }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        let heights = self.item_heights(width, parent_hints);
+        self.total_gap(heights.len()) + heights.into_iter().sum::<f32>()
     }
 }
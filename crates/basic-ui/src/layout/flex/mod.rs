@@ -6,7 +6,7 @@ use ui_composer_core::app::composition::layout::{
 use ui_composer_math::{
     flow::{
         CartesianFlow, CoordinateSystem as _, Flow, WritingFlow,
-        arrangers::arrange_stretchy_rects_with_minimum_sizes_dirty_alloc,
+        arrangers::arrange_stretchy_rects_with_size_limits,
     },
     prelude::{Rect, Size2, Vector2},
 };
@@ -20,12 +20,16 @@ where
     FlexContainer {
         items,
         flow_direction: Flow::Writing(WritingFlow::WritingAxisForward),
+        _main_axis_sizes_cache: None,
     }
 }
 
 pub struct FlexContainer<TItems: FlexItemList> {
     items: TItems,
     flow_direction: Flow,
+    /// The sizes computed in the last `prepare`, and the hints they were computed for,
+    /// so that `place` doesn't compute them again.
+    _main_axis_sizes_cache: Option<(ParentHints, Vec<f32>)>,
 }
 
 impl<TItems: FlexItemList> FlexContainer<TItems> {
@@ -46,6 +50,72 @@ impl<TItems: FlexItemList> FlexContainer<TItems> {
     }
 }
 
+impl<TItems: FlexItemList> FlexContainer<TItems> {
+    /// Splits the container among the items along the main axis,
+    /// using the hints they gave in their last `prepare`.
+    ///
+    /// If the items' natural sizes fit, those are the base sizes which grow to fill the container;
+    /// otherwise, items start at their minimum sizes. Either way, no item grows past its maximum size.
+    fn main_axis_sizes(
+        &mut self,
+        flow_direction: CartesianFlow,
+        parent_hints: ParentHints,
+    ) -> Vec<f32> {
+        let along = |size: Size2| {
+            if flow_direction.is_horizontal() {
+                size.width
+            } else {
+                size.height
+            }
+        };
+        let parent_size = along(parent_hints.rect.size);
+
+        let hints = self.items.hints().collect::<Vec<_>>();
+        let weights = self.items.weights().collect::<Vec<_>>();
+
+        let (minima, naturals) = if flow_direction.is_horizontal() {
+            let minima = self.items.minima(flow_direction).collect::<Vec<_>>();
+            let naturals = hints
+                .iter()
+                .map(|h| h.natural_size().width)
+                .collect::<Vec<_>>();
+            (minima, naturals)
+        } else {
+            // Items get the whole width, so wrapping ones can tell how tall they'll be.
+            let heights = self
+                .items
+                .heights_for_width(std::iter::repeat(parent_hints))
+                .collect::<Vec<_>>();
+            let naturals = hints
+                .iter()
+                .zip(heights.iter())
+                .map(|(h, height)| {
+                    h.with_measured_height(*height).natural_size().height
+                })
+                .collect::<Vec<_>>();
+            (heights, naturals)
+        };
+        let maxima = hints
+            .iter()
+            .map(|h| h.maximum_size.map_or(f32::INFINITY, along))
+            .collect::<Vec<_>>();
+
+        let bases = if naturals.iter().sum::<f32>() <= parent_size {
+            naturals
+        } else {
+            minima
+        };
+
+        arrange_stretchy_rects_with_size_limits(
+            parent_size,
+            weights.as_slice(),
+            bases.as_slice(),
+            maxima.as_slice(),
+            0.01,
+        )
+    }
+}
+
 impl<ItemList> LayoutItem for FlexContainer<ItemList>
 where
     ItemList: FlexItemList + Send,
@@ -64,72 +134,64 @@ where
 
         let base_hints = ParentHints {
             rect: Rect::new(parent_hints.rect.origin, mock_size),
-            ..parent_hints
+            ..parent_hints.clone()
         };
-        let base_hints_iter = std::iter::repeat_n(base_hints, ItemList::SIZE);
-        let _ = self.items.prepare(base_hints_iter).count();
+        // Items are prepared once per pass, everything after this uses the hints they gave.
+        let base_hints_iter = std::iter::repeat(base_hints);
+        let item_hints =
+            self.items.prepare(base_hints_iter).collect::<Vec<_>>();
 
-        let minima = self.items.minima(flow_direction).collect::<Vec<_>>();
-        let weights = self.items.weights().collect::<Vec<_>>();
-
-        let parent_size = if flow_direction.is_horizontal() {
-            parent_hints.rect.size.width
-        } else {
-            parent_hints.rect.size.height
-        };
         let main_axis_sizes =
-            arrange_stretchy_rects_with_minimum_sizes_dirty_alloc(
-                parent_size,
-                weights.as_slice(),
-                minima.as_slice(),
-                0.01,
-            );
-
-        let allocated_hints_iter = allocate_rects(
-            parent_hints,
-            flow_direction,
-            main_axis_sizes.into_iter(),
-        );
+            self.main_axis_sizes(flow_direction, parent_hints.clone());
+
+        let item_heights = self
+            .items
+            .heights_for_width(allocate_rects(
+                parent_hints.clone(),
+                flow_direction,
+                main_axis_sizes.iter().copied(),
+            ))
+            .collect::<Vec<_>>();
 
         let mut combined_minimum_sizes: Size2 = Size2::ZERO;
+        let mut combined_natural_sizes: Size2 = Size2::ZERO;
 
-        for h in self.items.prepare(allocated_hints_iter) {
+        for (h, height) in item_hints.into_iter().zip(item_heights) {
+            let h = h.with_measured_height(height);
+            let natural_size = h.natural_size();
             if flow_direction.is_horizontal() {
                 combined_minimum_sizes.width += h.minimum_size.width;
                 combined_minimum_sizes.height =
                     combined_minimum_sizes.height.max(h.minimum_size.height);
+                combined_natural_sizes.width += natural_size.width;
+                combined_natural_sizes.height =
+                    combined_natural_sizes.height.max(natural_size.height);
             } else {
                 combined_minimum_sizes.width =
                     combined_minimum_sizes.width.max(h.minimum_size.width);
                 combined_minimum_sizes.height += h.minimum_size.height;
+                combined_natural_sizes.width =
+                    combined_natural_sizes.width.max(natural_size.width);
+                combined_natural_sizes.height += natural_size.height;
             }
         }
 
-        ChildHints {
-            minimum_size: combined_minimum_sizes,
-        }
+        self._main_axis_sizes_cache = Some((parent_hints, main_axis_sizes));
+
+        ChildHints::new(combined_minimum_sizes)
+            .with_preferred_size(combined_natural_sizes)
     }
 
     fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
         let flow_direction =
             self.flow_direction.as_cartesian(&parent_hints.current_flow);
-        let minima = self.items.minima(flow_direction).collect::<Vec<_>>();
-        let weights = self.items.weights().collect::<Vec<_>>();
-
-        use CartesianFlow::*;
-        let parent_size = match flow_direction {
-            LeftToRight | RightToLeft => parent_hints.rect.size.width,
-            TopToBottom | BottomToTop => parent_hints.rect.size.height,
+        let main_axis_sizes = match self._main_axis_sizes_cache.take() {
+            Some((cached_hints, sizes)) if cached_hints == parent_hints => {
+                sizes
+            }
+            _ => self.main_axis_sizes(flow_direction, parent_hints.clone()),
         };
 
-        let main_axis_sizes =
-            arrange_stretchy_rects_with_minimum_sizes_dirty_alloc(
-                parent_size,
-                weights.as_slice(),
-                minima.as_slice(),
-                0.01,
-            );
-
         let parent_hints_iter = allocate_rects(
             parent_hints,
             flow_direction,
@@ -138,6 +200,39 @@ where
 
         self.items.place(parent_hints_iter)
     }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        let flow_direction =
+            self.flow_direction.as_cartesian(&parent_hints.current_flow);
+        let parent_hints = ParentHints {
+            rect: Rect::new(
+                parent_hints.rect.origin,
+                Size2::new(width, parent_hints.rect.size.height),
+            ),
+            ..parent_hints
+        };
+
+        if flow_direction.is_horizontal() {
+            // Items share the width, so the tallest one decides.
+            let main_axis_sizes =
+                self.main_axis_sizes(flow_direction, parent_hints.clone());
+            self.items
+                .heights_for_width(allocate_rects(
+                    parent_hints,
+                    flow_direction,
+                    main_axis_sizes.into_iter(),
+                ))
+                .fold(0.0, f32::max)
+        } else {
+            self.items
+                .heights_for_width(std::iter::repeat(parent_hints))
+                .sum()
+        }
+    }
 }
 
 fn allocate_rects<S>(
@@ -163,7 +258,7 @@ where
                         container.rect.size.height,
                     ),
                 ),
-                ..container
+                ..container.clone()
             },
             RightToLeft => ParentHints {
                 rect: Rect::new(
@@ -178,7 +273,7 @@ where
                         container.rect.size.height,
                     ),
                 ),
-                ..container
+                ..container.clone()
             },
             TopToBottom => ParentHints {
                 rect: Rect::new(
@@ -188,7 +283,7 @@ where
                         .translate(Vector2::new(0.0, *offset_from_start)),
                     Size2::new(container.rect.size.width, current_element_size),
                 ),
-                ..container
+                ..container.clone()
             },
             BottomToTop => ParentHints {
                 rect: Rect::new(
@@ -200,7 +295,7 @@ where
                     )),
                     Size2::new(container.rect.size.width, current_element_size),
                 ),
-                ..container
+                ..container.clone()
             },
         };
 
@@ -214,6 +309,9 @@ pub struct FlexItem<T> {
     item: T,
     grow: f32,
     _hints_cache: ChildHints,
+    /// The last width the item was measured for, and the height it needed,
+    /// so that it isn't measured again for the same width in the same pass.
+    _height_cache: Option<(f32, f32)>,
 }
 
 pub fn item<T>(item: T) -> FlexItem<T> {
@@ -221,6 +319,21 @@ pub fn item<T>(item: T) -> FlexItem<T> {
         item,
         grow: 0.0,
         _hints_cache: ChildHints::default(),
+        _height_cache: None,
+    }
+}
+
+impl<T: LayoutItem> FlexItem<T> {
+    fn height_for_width(&mut self, hints: ParentHints) -> f32 {
+        let width = hints.rect.size.width;
+        match self._height_cache {
+            Some((cached_width, height)) if cached_width == width => height,
+            _ => {
+                let height = self.item.height_for_width(width, hints);
+                self._height_cache = Some((width, height));
+                height
+            }
+        }
     }
 }
 
@@ -234,7 +347,7 @@ pub trait FlexItemList {
     type Content;
     type Weights: Iterator<Item = f32>;
     type Minima: Iterator<Item = f32>;
-    const SIZE: usize;
+    type Hints: Iterator<Item = ChildHints>;
 
     fn prepare<I>(
         &mut self,
@@ -247,6 +360,17 @@ pub trait FlexItemList {
 
     fn minima(&self, flow_direction: CartesianFlow) -> Self::Minima;
 
+    /// The hints each item gave in its last `prepare`.
+    fn hints(&self) -> Self::Hints;
+
+    /// How tall each item needs to be, given the width of the rect it's expected to get.
+    fn heights_for_width<I>(
+        &mut self,
+        expected_parent_hints: I,
+    ) -> impl Iterator<Item = f32>
+    where
+        I: Iterator<Item = ParentHints>;
+
     fn place<I>(&mut self, parent_hints: I) -> Self::Content
     where
         I: Iterator<Item = ParentHints>;
@@ -259,7 +383,7 @@ where
     type Content = A::Blueprint;
     type Weights = Once<f32>;
     type Minima = Once<f32>;
-    const SIZE: usize = 1;
+    type Hints = Once<ChildHints>;
 
     fn prepare<I>(
         &mut self,
@@ -274,6 +398,7 @@ where
                 .expect("Iterator underflow in FlexItem::prepare"),
         );
         self._hints_cache = hints;
+        self._height_cache = None;
         once(hints)
     }
 
@@ -293,12 +418,38 @@ where
         }
     }
 
+    fn hints(&self) -> Once<ChildHints> {
+        once(self._hints_cache)
+    }
+
+    fn heights_for_width<I>(
+        &mut self,
+        mut parent_hints: I,
+    ) -> impl Iterator<Item = f32>
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        let hints = parent_hints
+            .next()
+            .expect("Iterator underflow in FlexItem::heights_for_width");
+        once(self.height_for_width(hints))
+    }
+
     fn place<I>(&mut self, mut hx: I) -> Self::Content
     where
         I: Iterator<Item = ParentHints>,
     {
-        self.item
-            .place(hx.next().expect("Iterator underflow in FlexItem::place"))
+        let hx = hx.next().expect("Iterator underflow in FlexItem::place");
+
+        // The item might not want all the cross axis (or, if it has a maximum size, main axis) space it was given.
+        let height = self.height_for_width(hx.clone());
+        let rect = Rect::new(
+            hx.rect.origin,
+            self._hints_cache
+                .with_measured_height(height)
+                .fit(hx.rect.size),
+        );
+        self.item.place(ParentHints { rect, ..hx })
     }
 }
 
@@ -310,7 +461,7 @@ where
     type Content = (A::Content, B::Content);
     type Weights = Chain<A::Weights, B::Weights>;
     type Minima = Chain<A::Minima, B::Minima>;
-    const SIZE: usize = A::SIZE + B::SIZE;
+    type Hints = Chain<A::Hints, B::Hints>;
 
     fn prepare<I>(
         &mut self,
@@ -335,6 +486,22 @@ where
             .chain(self.1.minima(flow_direction))
     }
 
+    fn hints(&self) -> Self::Hints {
+        self.0.hints().chain(self.1.hints())
+    }
+
+    fn heights_for_width<I>(
+        &mut self,
+        mut parent_hints: I,
+    ) -> impl Iterator<Item = f32>
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        let a: Vec<_> = self.0.heights_for_width(&mut parent_hints).collect();
+        let b: Vec<_> = self.1.heights_for_width(parent_hints).collect();
+        a.into_iter().chain(b)
+    }
+
     fn place<I>(&mut self, mut parent_hints: I) -> Self::Content
     where
         I: Iterator<Item = ParentHints>,
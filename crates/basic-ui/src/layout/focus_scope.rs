@@ -0,0 +1,53 @@
+use ui_composer_core::app::composition::focus::{
+    FocusScope, FocusScopeBlueprint,
+};
+use ui_composer_core::app::composition::layout::{
+    LayoutItem,
+    hints::{ChildHints, ParentHints},
+};
+
+/// A container that moves keyboard focus among the [`Focusable`]s of `scope`
+/// with `Tab` and `Shift + Tab`, following the writing order of where it's placed.
+///
+/// [`Focusable`]: ui_composer_core::app::composition::focus::Focusable
+pub fn focus_scope<A>(scope: FocusScope, item: A) -> FocusScopeContainer<A>
+where
+    A: LayoutItem,
+{
+    FocusScopeContainer { scope, item }
+}
+
+pub struct FocusScopeContainer<A>
+where
+    A: LayoutItem,
+{
+    scope: FocusScope,
+    item: A,
+}
+
+impl<A> LayoutItem for FocusScopeContainer<A>
+where
+    A: LayoutItem,
+{
+    type Blueprint = FocusScopeBlueprint<A::Blueprint>;
+
+    fn prepare(&mut self, parent_hints: ParentHints) -> ChildHints {
+        self.item.prepare(parent_hints)
+    }
+
+    fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
+        self.scope.set_flow(parent_hints.current_flow);
+        FocusScopeBlueprint::new(
+            self.scope.clone(),
+            self.item.place(parent_hints),
+        )
+    }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        self.item.height_for_width(width, parent_hints)
+    }
+}
@@ -0,0 +1,198 @@
+use super::{FlexItem, FlexItemList, StackItemList};
+use ui_composer_core::app::composition::elements::keyed::KeyedVec;
+use ui_composer_core::app::composition::layout::{
+    LayoutItem,
+    hints::{ChildHints, ParentHints},
+};
+use ui_composer_math::flow::CartesianFlow;
+use ui_composer_state::futures_signals::signal_vec::MutableVec;
+
+/// A dynamic list of items for [`flex`](super::flex), [`row`](super::row)
+/// and [`column`](super::column), with one item for each value in `vec`.
+///
+/// Whenever the list is laid out, items are made (with `map`) for values
+/// that were added to `vec`, and dropped for values that were removed from it.
+/// Items for values that were kept are reused, and so are their elements,
+/// since the list places a [`KeyedVec`] keyed by the values themselves.
+///
+/// The list only looks at `vec` when it's laid out, so put its container
+/// in a [`Relayout`](ui_composer_core::app::composition::layout::relayout::Relayout)
+/// to have it laid out again when `vec` changes.
+///
+/// ```rust,ignore
+/// let todos = MutableVec::new_with_values(vec!["Buy milk", "Walk the dog"]);
+///
+/// flex(for_each(todos.clone(), |todo| item(text(todo)).with_grow(1.0)))
+///     .with_vertical_flow()
+///     .relayout_on(move || todos.signal_vec_cloned().to_signal_map(|_| ()))
+/// ```
+pub fn for_each<T, F, Item>(vec: MutableVec<T>, map: F) -> ForEach<T, F, Item>
+where
+    F: FnMut(T) -> Item,
+{
+    ForEach {
+        vec,
+        map,
+        items: Vec::new(),
+    }
+}
+
+pub struct ForEach<T, F, Item> {
+    vec: MutableVec<T>,
+    map: F,
+    items: Vec<(T, Item)>,
+}
+
+impl<T, F, Item> ForEach<T, F, Item>
+where
+    T: Clone + PartialEq,
+    F: FnMut(T) -> Item,
+{
+    /// Brings the items up to date with the values in `vec`.
+    fn refresh(&mut self) {
+        // Copied out so that `map` is free to lock `vec` itself.
+        let values = self.vec.lock_ref().to_vec();
+        let mut old_items = core::mem::take(&mut self.items);
+
+        for value in values {
+            let item = match old_items.iter().position(|(old, _)| *old == value)
+            {
+                Some(index) => old_items.remove(index).1,
+                None => (self.map)(value.clone()),
+            };
+            self.items.push((value, item));
+        }
+    }
+}
+
+impl<T, F, A> FlexItemList for ForEach<T, F, FlexItem<A>>
+where
+    T: Clone + PartialEq,
+    F: FnMut(T) -> FlexItem<A>,
+    A: LayoutItem,
+{
+    type Content = KeyedVec<T, A::Blueprint>;
+    type Weights = std::vec::IntoIter<f32>;
+    type Minima = std::vec::IntoIter<f32>;
+    type Hints = std::vec::IntoIter<ChildHints>;
+
+    fn prepare<I>(
+        &mut self,
+        mut expected_parent_hints: I,
+    ) -> impl Iterator<Item = ChildHints>
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        self.refresh();
+
+        let mut hints = Vec::with_capacity(self.items.len());
+        for (_, item) in self.items.iter_mut() {
+            hints.extend(item.prepare(&mut expected_parent_hints));
+        }
+        hints.into_iter()
+    }
+
+    fn weights(&self) -> Self::Weights {
+        self.items
+            .iter()
+            .flat_map(|(_, item)| item.weights())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn minima(&self, flow_direction: CartesianFlow) -> Self::Minima {
+        self.items
+            .iter()
+            .flat_map(|(_, item)| item.minima(flow_direction))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn hints(&self) -> Self::Hints {
+        self.items
+            .iter()
+            .flat_map(|(_, item)| item.hints())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn heights_for_width<I>(
+        &mut self,
+        mut expected_parent_hints: I,
+    ) -> impl Iterator<Item = f32>
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        let mut heights = Vec::with_capacity(self.items.len());
+        for (_, item) in self.items.iter_mut() {
+            heights.extend(item.heights_for_width(&mut expected_parent_hints));
+        }
+        heights.into_iter()
+    }
+
+    fn place<I>(&mut self, parent_hints: I) -> Self::Content
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        // Items added to `vec` since the list was prepared have no rects yet,
+        // they'll be placed the next time the list is laid out.
+        self.items
+            .iter_mut()
+            .zip(parent_hints)
+            .map(|((value, item), hints)| {
+                (
+                    value.clone(),
+                    FlexItemList::place(item, core::iter::once(hints)),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<T, F, A> StackItemList for ForEach<T, F, A>
+where
+    T: Clone + PartialEq,
+    F: FnMut(T) -> A,
+    A: LayoutItem,
+{
+    type Content = KeyedVec<T, A::Blueprint>;
+
+    fn prepare(
+        &mut self,
+        expected_parent_hints: ParentHints,
+        hints: &mut Vec<ChildHints>,
+    ) {
+        self.refresh();
+
+        hints.extend(
+            self.items
+                .iter_mut()
+                .map(|(_, item)| item.prepare(expected_parent_hints.clone())),
+        );
+    }
+
+    fn heights_for_width<I>(
+        &mut self,
+        expected_parent_hints: &mut I,
+        heights: &mut Vec<f32>,
+    ) where
+        I: Iterator<Item = ParentHints>,
+    {
+        heights.extend(self.items.iter_mut().zip(expected_parent_hints).map(
+            |((_, item), hints)| {
+                item.height_for_width(hints.rect.size.width, hints)
+            },
+        ));
+    }
+
+    fn place<I>(&mut self, parent_hints: &mut I) -> Self::Content
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        self.items
+            .iter_mut()
+            .zip(parent_hints)
+            .map(|((value, item), hints)| (value.clone(), item.place(hints)))
+            .collect()
+    }
+}
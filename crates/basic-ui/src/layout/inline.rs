@@ -81,7 +81,7 @@ impl<T: LayoutItem> InlineItem for InlineAdapter<T> {
         cx: &mut InlineContext,
         hints: ParentHints,
     ) -> Self::Blueprint {
-        let inner_hints = self.0.prepare(hints);
+        let inner_hints = self.0.prepare(hints.clone());
         let size = inner_hints.minimum_size;
         let (w, h) = (size.width as Offset, size.height as Offset);
 
@@ -118,6 +118,10 @@ impl<T: LayoutItem> InlineItem for InlineAdapter<T> {
     }
 }
 
+/// Text laid out word by word, in a monospace font.
+///
+/// If the [cascading context](ui_composer_core::app::composition::layout::hints::CascadingContext)
+/// has a text color, it is used instead of this text's own color.
 pub struct MonospaceText(pub String, pub Srgba);
 
 impl InlineItem for MonospaceText {
@@ -126,8 +130,9 @@ impl InlineItem for MonospaceText {
     fn allocate(
         &mut self,
         cx: &mut InlineContext,
-        _: ParentHints,
+        hints: ParentHints,
     ) -> Self::Blueprint {
+        let color = hints.context.text_color.unwrap_or(self.1);
         let word_spacing = 1;
         let mut words_with_pos = Vec::new();
         let words = self.0.split_whitespace();
@@ -156,7 +161,7 @@ impl InlineItem for MonospaceText {
                             Size2::new(len as f32, 1.0),
                         ),
                     )
-                    .with_color(self.1),
+                    .with_color(color),
             );
             cx.max_line_height = cx.max_line_height.max(1);
             cx.offset.x += len;
@@ -214,10 +219,13 @@ where
         cx: &mut InlineContext,
         hints: ParentHints,
     ) -> Self::Blueprints {
-        (self.0.allocate(cx, hints), self.1.allocate(cx, hints))
+        (
+            self.0.allocate(cx, hints.clone()),
+            self.1.allocate(cx, hints),
+        )
     }
     fn measure(&mut self, cx: &mut MeasureContext, hints: ParentHints) {
-        self.0.measure(cx, hints);
+        self.0.measure(cx, hints.clone());
         self.1.measure(cx, hints);
     }
 }
@@ -242,27 +250,33 @@ impl<T: InlineItemList + Send> LayoutItem for LinewiseFlow<T> {
             offset: Vector2::new(0, 0),
             max_width_reached: 0,
         };
-        self.items.measure(&mut min_w_cx, parent_hints);
+        self.items.measure(&mut min_w_cx, parent_hints.clone());
         let true_min_w = min_w_cx.max_width_reached;
 
-        let mut height_whem_min_w_cx = MeasureContext {
-            container_width: true_min_w,
+        // Without knowing our width, the best guess is being as narrow as possible.
+        // Containers that do know it will ask `height_for_width` instead.
+        let height_when_min_w =
+            self.height_for_width(true_min_w as f32, parent_hints);
+
+        ChildHints::new(Size2::new(true_min_w as f32, height_when_min_w))
+    }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        let mut cx = MeasureContext {
+            container_width: width as Offset,
             inline_gap: self.inline_gap,
             cross_axis_gap: self.cross_axis_gap,
             max_line_height: 1,
             offset: Vector2::new(0, 0),
             max_width_reached: 0,
         };
-        self.items.measure(&mut height_whem_min_w_cx, parent_hints);
-        let height_when_min_w = height_whem_min_w_cx.offset.y
-            + height_whem_min_w_cx.max_line_height;
-
-        ChildHints {
-            minimum_size: Size2::new(
-                true_min_w as f32,
-                height_when_min_w as f32,
-            ),
-        }
+        self.items.measure(&mut cx, parent_hints);
+
+        (cx.offset.y + cx.max_line_height) as f32
     }
 
     fn place(&mut self, hints: ParentHints) -> Self::Blueprint {
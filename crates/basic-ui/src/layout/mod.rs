@@ -27,10 +27,30 @@ mod center;
 #[doc(inline)]
 pub use center::*;
 
+mod cascade;
+#[doc(inline)]
+pub use cascade::*;
+
+mod focus_scope;
+#[doc(inline)]
+pub use focus_scope::*;
+
+mod pointer_scope;
+#[doc(inline)]
+pub use pointer_scope::*;
+
 mod with_size;
 #[doc(inline)]
 pub use with_size::*;
 
+mod stack;
+#[doc(inline)]
+pub use stack::*;
+
+mod for_each;
+#[doc(inline)]
+pub use for_each::*;
+
 mod row;
 #[doc(inline)]
 pub use row::*;
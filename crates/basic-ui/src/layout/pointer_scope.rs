@@ -0,0 +1,50 @@
+use ui_composer_core::app::composition::layout::{
+    LayoutItem,
+    hints::{ChildHints, ParentHints},
+};
+use ui_composer_core::app::composition::pointer::{
+    PointerScopeBlueprint, Pointers,
+};
+
+/// A container that keeps `pointers` up to date with the cursor events its item receives,
+/// so interactors given the same [`Pointers`] know where every pointer is.
+pub fn pointer_scope<A>(pointers: Pointers, item: A) -> PointerScopeContainer<A>
+where
+    A: LayoutItem,
+{
+    PointerScopeContainer { pointers, item }
+}
+
+pub struct PointerScopeContainer<A>
+where
+    A: LayoutItem,
+{
+    pointers: Pointers,
+    item: A,
+}
+
+impl<A> LayoutItem for PointerScopeContainer<A>
+where
+    A: LayoutItem,
+{
+    type Blueprint = PointerScopeBlueprint<A::Blueprint>;
+
+    fn prepare(&mut self, parent_hints: ParentHints) -> ChildHints {
+        self.item.prepare(parent_hints)
+    }
+
+    fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
+        PointerScopeBlueprint::new(
+            self.pointers.clone(),
+            self.item.place(parent_hints),
+        )
+    }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        self.item.height_for_width(width, parent_hints)
+    }
+}
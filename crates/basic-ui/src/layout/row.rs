@@ -1,5 +1,6 @@
 use core::f32;
 
+use super::StackItemList;
 use ui_composer_core::app::composition::layout::{
     LayoutItem,
     hints::{ChildHints, ParentHints},
@@ -13,37 +14,74 @@ use ui_composer_math::prelude::{Rect, Size2, Vector2};
 /// of the items inside (accounting for gap).
 ///
 /// The height of the container is the max height between the items.
-/// TODO: Allow it to take more than two items.
-pub fn row<A, B>((item_a, item_b): (A, B)) -> RowContainer<A, B> {
+pub fn row<Items: StackItemList>(items: Items) -> RowContainer<Items> {
     RowContainer {
-        item_a,
-        item_b,
+        items,
         gap: 0.0,
-        __item_a_hints_cache: ChildHints::default(),
-        __item_b_hints_cache: ChildHints::default(),
+        __items_hints_cache: Vec::new(),
     }
 }
 
-pub struct RowContainer<A, B> {
-    pub item_a: A,
-    pub item_b: B,
+pub struct RowContainer<Items> {
+    pub items: Items,
     pub gap: f32,
-    __item_a_hints_cache: ChildHints,
-    __item_b_hints_cache: ChildHints,
+    __items_hints_cache: Vec<ChildHints>,
 }
 
-impl<A, B> RowContainer<A, B> {
+impl<Items> RowContainer<Items> {
     pub fn with_gap(self, gap: f32) -> Self {
         Self { gap, ..self }
     }
 }
 
-impl<A, B> LayoutItem for RowContainer<A, B>
+impl<Items> RowContainer<Items>
 where
-    A: LayoutItem,
-    B: LayoutItem,
+    Items: StackItemList,
 {
-    type Blueprint = (A::Blueprint, B::Blueprint);
+    /// Rects for each item, which get their natural width,
+    /// and as much height as they'll take.
+    fn item_rects(&self, parent_hints: ParentHints) -> Vec<Rect> {
+        let mut offset = 0.0;
+        self.__items_hints_cache
+            .iter()
+            .map(|hints| {
+                let size = hints.fit(Size2::new(
+                    hints.natural_size().width,
+                    parent_hints.rect.size.height,
+                ));
+                let rect = Rect::new(
+                    parent_hints
+                        .rect
+                        .origin
+                        .translate(Vector2::new(offset, 0.0)),
+                    size,
+                );
+                offset += size.width + self.gap;
+                rect
+            })
+            .collect()
+    }
+
+    /// How tall each item needs to be at its natural width.
+    fn item_heights(&mut self, parent_hints: ParentHints) -> Vec<f32> {
+        let rects = self.item_rects(parent_hints.clone());
+        let mut heights = Vec::new();
+        self.items.heights_for_width(
+            &mut rects.into_iter().map(|rect| ParentHints {
+                rect,
+                ..parent_hints.clone()
+            }),
+            &mut heights,
+        );
+        heights
+    }
+}
+
+impl<Items> LayoutItem for RowContainer<Items>
+where
+    Items: StackItemList + Send,
+{
+    type Blueprint = Items::Content;
 
     fn prepare(&mut self, parent_hints: ParentHints) -> ChildHints {
         let inner_hints = ParentHints {
@@ -51,47 +89,42 @@ where
                 parent_hints.rect.origin,
                 Size2::new(f32::INFINITY, parent_hints.rect.size.height),
             ),
-            ..parent_hints
+            ..parent_hints.clone()
         };
-        let a = self.item_a.prepare(inner_hints);
-        let b = self.item_b.prepare(inner_hints);
+        let mut hints = Vec::new();
+        self.items.prepare(inner_hints, &mut hints);
+        self.__items_hints_cache = hints;
 
-        self.__item_a_hints_cache = a;
-        self.__item_b_hints_cache = b;
+        // Wrapping items can tell how tall they'll be at their natural width.
+        let heights = self.item_heights(parent_hints);
+        let hints = &mut self.__items_hints_cache;
+        for (hints, height) in hints.iter_mut().zip(heights) {
+            *hints = hints.with_measured_height(height);
+        }
 
-        let minimum_size = Size2::new(
-            a.minimum_size.width + self.gap + b.minimum_size.width, // Min width with gap
-            a.minimum_size.height.max(b.minimum_size.height), // Max height
-        );
-        ChildHints { minimum_size }
+        let total_gap = self.gap * hints.len().saturating_sub(1) as f32;
+        let mut minimum_size = Size2::<f32>::new(total_gap, 0.0);
+        let mut preferred_size = Size2::<f32>::new(total_gap, 0.0);
+        for hints in hints.iter() {
+            let natural_size = hints.natural_size();
+            minimum_size.width += hints.minimum_size.width;
+            minimum_size.height =
+                minimum_size.height.max(hints.minimum_size.height);
+            preferred_size.width += natural_size.width;
+            preferred_size.height =
+                preferred_size.height.max(natural_size.height);
+        }
+
+        ChildHints::new(minimum_size).with_preferred_size(preferred_size)
     }
 
     fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
-        let a = self.item_a.place(ParentHints {
-            rect: Rect::new(
-                parent_hints.rect.origin,
-                Size2::new(
-                    self.__item_a_hints_cache.minimum_size.width,
-                    parent_hints.rect.size.height,
-                ),
-            ),
-            ..parent_hints
-        });
-
-        let b = self.item_b.place(ParentHints {
-            rect: Rect::new(
-                parent_hints.rect.origin.translate(Vector2::new(
-                    self.__item_a_hints_cache.minimum_size.width + self.gap,
-                    0.0,
-                )),
-                Size2::new(
-                    self.__item_b_hints_cache.minimum_size.width,
-                    parent_hints.rect.size.height,
-                ),
-            ),
-            ..parent_hints
-        });
+        let rects = self.item_rects(parent_hints.clone());
 
-        (a, b)
+        self.items
+            .place(&mut rects.into_iter().map(|rect| ParentHints {
+                rect,
+                ..parent_hints.clone()
+            }))
     }
 }
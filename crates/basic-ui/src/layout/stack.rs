@@ -0,0 +1,212 @@
+use ui_composer_core::app::composition::layout::{
+    LayoutItem,
+    hints::{ChildHints, ParentHints},
+};
+
+/// A list of items for [`row`](super::row) and [`column`](super::column).
+///
+/// Items can be put in lists in three ways, which can be mixed freely:
+/// - Cons lists of [`stacked`] items, like `(stacked(a), (stacked(b), stacked(c)))`;
+/// - [`Vec`]s of items of the same type;
+/// - Arrays of items of the same type.
+///
+/// A bare `(a, b)` is not a list, since it's already a [`LayoutItem`] on its own
+/// (the two items drawn on top of each other).
+/// In `view!`, that means each child of a `column [...]` or `row [...]` is written
+/// as `stacked child`, just like the children of a `flex [...]` are written as `item child`.
+pub trait StackItemList {
+    type Content;
+
+    /// Prepares every item of the list with the same hints, in order.
+    fn prepare(
+        &mut self,
+        expected_parent_hints: ParentHints,
+        hints: &mut Vec<ChildHints>,
+    );
+
+    /// Measures how tall every item of the list needs to be,
+    /// each with the width of the rect it's expected to get.
+    fn heights_for_width<I>(
+        &mut self,
+        expected_parent_hints: &mut I,
+        heights: &mut Vec<f32>,
+    ) where
+        I: Iterator<Item = ParentHints>;
+
+    fn place<I>(&mut self, parent_hints: &mut I) -> Self::Content
+    where
+        I: Iterator<Item = ParentHints>;
+}
+
+/// Makes a layout item an element of a [`StackItemList`].
+pub fn stacked<T>(item: T) -> StackAdapter<T> {
+    StackAdapter(item)
+}
+
+pub struct StackAdapter<T>(pub T);
+
+impl<T: LayoutItem> StackItemList for StackAdapter<T> {
+    type Content = T::Blueprint;
+
+    fn prepare(
+        &mut self,
+        expected_parent_hints: ParentHints,
+        hints: &mut Vec<ChildHints>,
+    ) {
+        hints.push(self.0.prepare(expected_parent_hints));
+    }
+
+    fn heights_for_width<I>(
+        &mut self,
+        expected_parent_hints: &mut I,
+        heights: &mut Vec<f32>,
+    ) where
+        I: Iterator<Item = ParentHints>,
+    {
+        heights.push(measure(&mut self.0, expected_parent_hints));
+    }
+
+    fn place<I>(&mut self, parent_hints: &mut I) -> Self::Content
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        self.0.place(
+            parent_hints
+                .next()
+                .expect("Iterator underflow in StackAdapter::place"),
+        )
+    }
+}
+
+impl<A, B> StackItemList for (A, B)
+where
+    A: StackItemList,
+    B: StackItemList,
+{
+    type Content = (A::Content, B::Content);
+
+    fn prepare(
+        &mut self,
+        expected_parent_hints: ParentHints,
+        hints: &mut Vec<ChildHints>,
+    ) {
+        self.0.prepare(expected_parent_hints.clone(), hints);
+        self.1.prepare(expected_parent_hints, hints);
+    }
+
+    fn heights_for_width<I>(
+        &mut self,
+        expected_parent_hints: &mut I,
+        heights: &mut Vec<f32>,
+    ) where
+        I: Iterator<Item = ParentHints>,
+    {
+        self.0.heights_for_width(expected_parent_hints, heights);
+        self.1.heights_for_width(expected_parent_hints, heights);
+    }
+
+    fn place<I>(&mut self, parent_hints: &mut I) -> Self::Content
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        let a = self.0.place(parent_hints);
+        let b = self.1.place(parent_hints);
+        (a, b)
+    }
+}
+
+impl<T: LayoutItem> StackItemList for Vec<T> {
+    type Content = Vec<T::Blueprint>;
+
+    fn prepare(
+        &mut self,
+        expected_parent_hints: ParentHints,
+        hints: &mut Vec<ChildHints>,
+    ) {
+        hints.extend(
+            self.iter_mut()
+                .map(|item| item.prepare(expected_parent_hints.clone())),
+        );
+    }
+
+    fn heights_for_width<I>(
+        &mut self,
+        expected_parent_hints: &mut I,
+        heights: &mut Vec<f32>,
+    ) where
+        I: Iterator<Item = ParentHints>,
+    {
+        for item in self.iter_mut() {
+            heights.push(measure(item, expected_parent_hints));
+        }
+    }
+
+    fn place<I>(&mut self, parent_hints: &mut I) -> Self::Content
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        place_each(self, parent_hints)
+    }
+}
+
+impl<T: LayoutItem, const N: usize> StackItemList for [T; N] {
+    type Content = Vec<T::Blueprint>;
+
+    fn prepare(
+        &mut self,
+        expected_parent_hints: ParentHints,
+        hints: &mut Vec<ChildHints>,
+    ) {
+        hints.extend(
+            self.iter_mut()
+                .map(|item| item.prepare(expected_parent_hints.clone())),
+        );
+    }
+
+    fn heights_for_width<I>(
+        &mut self,
+        expected_parent_hints: &mut I,
+        heights: &mut Vec<f32>,
+    ) where
+        I: Iterator<Item = ParentHints>,
+    {
+        for item in self.iter_mut() {
+            heights.push(measure(item, expected_parent_hints));
+        }
+    }
+
+    fn place<I>(&mut self, parent_hints: &mut I) -> Self::Content
+    where
+        I: Iterator<Item = ParentHints>,
+    {
+        place_each(self, parent_hints)
+    }
+}
+
+fn place_each<T, I>(items: &mut [T], parent_hints: &mut I) -> Vec<T::Blueprint>
+where
+    T: LayoutItem,
+    I: Iterator<Item = ParentHints>,
+{
+    items
+        .iter_mut()
+        .map(|item| {
+            item.place(
+                parent_hints
+                    .next()
+                    .expect("Iterator underflow in StackItemList::place"),
+            )
+        })
+        .collect()
+}
+
+fn measure<T, I>(item: &mut T, expected_parent_hints: &mut I) -> f32
+where
+    T: LayoutItem,
+    I: Iterator<Item = ParentHints>,
+{
+    let hints = expected_parent_hints
+        .next()
+        .expect("Iterator underflow in StackItemList::heights_for_width");
+    item.height_for_width(hints.rect.size.width, hints)
+}
@@ -42,17 +42,22 @@ where
 {
     type Blueprint = A::Blueprint;
 
-    fn prepare(
-        &mut self,
-        parent_hints: ParentHints,
-    ) -> ui_composer_core::app::composition::layout::hints::ChildHints {
+    fn prepare(&mut self, parent_hints: ParentHints) -> ChildHints {
         let inner = self.item.prepare(parent_hints);
-        ChildHints {
-            minimum_size: self.suggested_size.max(inner.minimum_size),
-        }
+        inner.with_minimum_size(self.suggested_size.max(inner.minimum_size))
     }
 
     fn place(&mut self, layout_hints: ParentHints) -> Self::Blueprint {
         self.item.place(layout_hints)
     }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        self.item
+            .height_for_width(width, parent_hints)
+            .max(self.suggested_size.height)
+    }
 }
@@ -1,25 +1,30 @@
-use ui_composer_core::app::composition::{
-    algebra::{Bubble, Empty},
-    effects::ElementEffect,
-    elements::{Blueprint, Element},
-    visit::{Apply, DriveThru},
+use std::mem::MaybeUninit;
+use ui_composer_core::app::{
+    composition::{
+        algebra::Gather,
+        effects::ElementEffect,
+        elements::{Element, Environment},
+        visit::Apply,
+    },
+    runner::headless::HeadlessEnvironment,
 };
-use ui_composer_input::event::Event;
+use ui_composer_derive_ui::{Blueprint, Bubble, DriveThru, Element};
 use ui_composer_math::prelude::{Mix, Rect, Srgba};
 use ui_composer_platform_tui::{
     canvas::{Canvas as _, TextModePixel},
     nodes::TerminalEffectVisitor,
-    runner::{TerminalBlueprintResources, TerminalEnvironment},
+    runner::TerminalEnvironment,
 };
-use ui_composer_platform_winit::runner::{WinitBlueprintResources, WinitEnvironment};
 
 /// An effect that describes rendering of a quad in the terminal.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, DriveThru)]
+#[drive_thru(visit)]
 pub struct RenderQuad(pub Rect, pub Srgba);
 
 //impl ElementEffect<WinitEnvironment> for RenderQuad {}
 
 impl ElementEffect<TerminalEnvironment> for RenderQuad {}
+impl ElementEffect<HeadlessEnvironment> for RenderQuad {}
 impl<'fx> Apply<RenderQuad> for TerminalEffectVisitor<'fx> {
     fn visit(&mut self, RenderQuad(rect, color): &RenderQuad) {
         self.canvas.rect(
@@ -37,12 +42,63 @@ impl Apply<RenderQuad> for () {
         /* Do nothing for now */
     }
 }
-impl<V> DriveThru<V> for RenderQuad
-where
-    V: Apply<Self>,
-{
-    fn drive_thru(&self, visitor: &mut V) {
-        visitor.visit(self);
+// SAFETY: The single item of the buffer is initialized with this quad.
+unsafe impl<Cx> Gather<Cx, RenderQuad> for RenderQuad {
+    fn size(&self) -> usize {
+        1
+    }
+
+    fn gather(&mut self, _: &mut Cx, acc: &mut [MaybeUninit<RenderQuad>]) {
+        acc[0].write(self.clone());
+    }
+}
+
+/// A visitor that gathers every [`RenderQuad`] of a frame into one contiguous buffer,
+/// so that a renderer can upload them as instances in a single batch.
+///
+/// Other effects are skipped.
+#[derive(Debug, Default, Clone)]
+pub struct QuadBatch {
+    pub quads: Vec<RenderQuad>,
+}
+
+impl QuadBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empties the batch, keeping its allocation for the next frame.
+    pub fn clear(&mut self) {
+        self.quads.clear();
+    }
+
+    /// Gathers the quads of an effect that can count them beforehand,
+    /// growing the buffer (at most) once.
+    pub fn gather<Fx: Gather<(), RenderQuad>>(&mut self, effect: &mut Fx) {
+        effect.gather_into(&mut (), &mut self.quads);
+    }
+
+    /// Gathers the quads `element` shows right now, which is how a renderer
+    /// gets every quad of a frame.
+    pub fn gather_element<Env, E>(&mut self, element: &E)
+    where
+        Env: Environment,
+        E: Element<Env>,
+        for<'fx> E::Effect<'fx>: Gather<(), RenderQuad>,
+    {
+        self.gather(&mut element.effect());
+    }
+}
+
+impl Apply<RenderQuad> for QuadBatch {
+    fn visit(&mut self, quad: &RenderQuad) {
+        self.quads.push(quad.clone());
+    }
+}
+
+impl From<&Graphic> for RenderQuad {
+    fn from(graphic: &Graphic) -> Self {
+        RenderQuad(graphic.rect, graphic.color)
     }
 }
 
@@ -55,7 +111,8 @@ pub fn Graphic() -> Graphic {
 }
 
 /// A simple coloured graphic.
-#[derive(Default, Clone, Copy, PartialEq)]
+#[derive(Default, Clone, Copy, PartialEq, Blueprint, Element, Bubble)]
+#[element(effect = RenderQuad)]
 pub struct Graphic {
     pub rect: Rect,
     pub color: Srgba,
@@ -86,28 +143,6 @@ impl Graphic {
     }
 }
 
-impl Bubble<Event, bool> for Graphic {
-    fn bubble(&mut self, _: &mut Event) -> bool {
-        Empty::empty()
-    }
-}
-
-impl Blueprint<TerminalEnvironment> for Graphic {
-    type Element = Self;
-
-    fn make(self, _: &TerminalBlueprintResources) -> Self::Element {
-        self
-    }
-}
-
-impl Element<TerminalEnvironment> for Graphic {
-    type Effect<'fx> = RenderQuad;
-
-    fn effect(&self) -> Self::Effect<'_> {
-        RenderQuad(self.rect, self.color)
-    }
-}
-
 impl ui_composer_state::effect::animation::Lerp for Graphic {
     fn linear_interpolate(self, other: Self, t: f32) -> Self {
         Graphic {
@@ -116,19 +151,3 @@ impl ui_composer_state::effect::animation::Lerp for Graphic {
         }
     }
 }
-
-impl Blueprint<WinitEnvironment> for Graphic {
-    type Element = Self;
-
-    fn make(self, _: &WinitBlueprintResources<'_>) -> Self::Element {
-        self
-    }
-}
-
-impl Element<WinitEnvironment> for Graphic {
-    type Effect<'fx> = RenderQuad;
-
-    fn effect(&self) -> Self::Effect<'_> {
-        RenderQuad(self.rect, self.color)
-    }
-}
\ No newline at end of file
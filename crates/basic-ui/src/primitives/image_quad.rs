@@ -1,13 +1,12 @@
+use std::mem::MaybeUninit;
 use std::sync::Arc;
 
 use image::{DynamicImage, GenericImageView};
-use ui_composer_core::app::composition::{
-    algebra::{Bubble, Empty},
-    effects::ElementEffect,
-    elements::Element,
-    visit::{Apply, DriveThru},
+use ui_composer_core::app::{
+    composition::{algebra::Gather, effects::ElementEffect, visit::Apply},
+    runner::headless::HeadlessEnvironment,
 };
-use ui_composer_input::event::Event;
+use ui_composer_derive_ui::{Bubble, DriveThru, Element};
 use ui_composer_math::prelude::{Rect, Srgba};
 use ui_composer_platform_tui::{
     canvas::{Canvas as _, TextModePixel},
@@ -15,10 +14,12 @@ use ui_composer_platform_tui::{
     runner::TerminalEnvironment,
 };
 
+use super::graphic::{QuadBatch, RenderQuad};
 use crate::components::ImageViewBlueprint;
 
 /// An effect that describes rendering of a quad in the terminal.
-#[derive(Debug)]
+#[derive(Debug, Clone, DriveThru)]
+#[drive_thru(visit)]
 pub struct RenderImageQuad(
     pub Rect,
     // TODO: Maybe use a & instead of an arc here.
@@ -28,6 +29,7 @@ pub struct RenderImageQuad(
 //impl ElementEffect<WinitEnvironment> for RenderImageQuad {}
 
 impl ElementEffect<TerminalEnvironment> for RenderImageQuad {}
+impl ElementEffect<HeadlessEnvironment> for RenderImageQuad {}
 impl<'fx> Apply<RenderImageQuad> for TerminalEffectVisitor<'fx> {
     fn visit(&mut self, RenderImageQuad(rect, image): &RenderImageQuad) {
         self.canvas.quad(rect.as_(), |input| {
@@ -54,12 +56,22 @@ impl Apply<RenderImageQuad> for () {
         /* Do nothing for now */
     }
 }
-impl<V> DriveThru<V> for RenderImageQuad
-where
-    V: Apply<Self>,
-{
-    fn drive_thru(&self, visitor: &mut V) {
-        visitor.visit(self);
+impl Apply<RenderImageQuad> for QuadBatch {
+    fn visit(&mut self, _: &RenderImageQuad) {
+        /* Not a quad! */
+    }
+}
+// SAFETY: There's nothing to initialize.
+unsafe impl<Cx> Gather<Cx, RenderQuad> for RenderImageQuad {
+    fn size(&self) -> usize {
+        0
+    }
+
+    fn gather(&mut self, _: &mut Cx, _: &mut [MaybeUninit<RenderQuad>]) {}
+}
+impl From<&ImageViewElementTerminal> for RenderImageQuad {
+    fn from(element: &ImageViewElementTerminal) -> Self {
+        RenderImageQuad(element.rect, element.image.clone())
     }
 }
 
@@ -72,20 +84,12 @@ pub fn ImageView() -> ImageViewBlueprint {
 }
 
 /// A simple image
+#[derive(Element, Bubble)]
+#[element(effect = RenderImageQuad)]
 pub struct ImageViewElementTerminal {
     image: std::sync::Arc<DynamicImage>,
     rect: Rect,
 }
-impl Element<TerminalEnvironment> for ImageViewElementTerminal {
-    type Effect<'fx>
-        = RenderImageQuad
-    where
-        Self: 'fx;
-
-    fn effect(&self) -> Self::Effect<'_> {
-        RenderImageQuad(self.rect, self.image.clone())
-    }
-}
 
 impl ImageViewElementTerminal {
     pub fn new(rect: Rect, image: std::sync::Arc<DynamicImage>) -> Self {
@@ -102,9 +106,3 @@ impl ImageViewElementTerminal {
         Self { rect, ..self }
     }
 }
-
-impl Bubble<Event, bool> for ImageViewElementTerminal {
-    fn bubble(&mut self, _: &mut Event) -> bool {
-        Empty::empty()
-    }
-}
@@ -1,26 +1,29 @@
-use ui_composer_core::app::composition::{
-    algebra::{Bubble, Empty},
-    effects::ElementEffect,
-    elements::{Blueprint, Element},
-    visit::{Apply, DriveThru},
+use super::graphic::{QuadBatch, RenderQuad};
+use std::mem::MaybeUninit;
+use ui_composer_core::app::{
+    composition::{algebra::Gather, effects::ElementEffect, visit::Apply},
+    runner::headless::HeadlessEnvironment,
 };
-use ui_composer_input::event::Event;
+use ui_composer_derive_ui::{Blueprint, Bubble, DriveThru, Element};
 use ui_composer_math::prelude::{Point2, Rect, Srgba};
 use ui_composer_platform_tui::{
     canvas::{Canvas as _, TextModePixel},
     nodes::TerminalEffectVisitor,
-    runner::{TerminalBlueprintResources, TerminalEnvironment},
+    runner::TerminalEnvironment,
 };
 use ui_composer_platform_winit::runner::WinitEnvironment;
 
 /// An effect that describes rendering some text in the terminal.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, DriveThru)]
+#[drive_thru(visit)]
 pub struct RenderText(pub Rect, pub String, pub Srgba);
 
 impl ElementEffect<WinitEnvironment> for RenderText {}
 
 impl ElementEffect<TerminalEnvironment> for RenderText {}
 
+impl ElementEffect<HeadlessEnvironment> for RenderText {}
+
 impl<'fx> Apply<RenderText> for TerminalEffectVisitor<'fx> {
     fn visit(&mut self, RenderText(rect, text, color): &RenderText) {
         let rect = rect.as_::<u32>();
@@ -37,12 +40,22 @@ impl<'fx> Apply<RenderText> for TerminalEffectVisitor<'fx> {
         }
     }
 }
-impl<V> DriveThru<V> for RenderText
-where
-    V: Apply<Self>,
-{
-    fn drive_thru(&self, visitor: &mut V) {
-        visitor.visit(self);
+impl Apply<RenderText> for QuadBatch {
+    fn visit(&mut self, _: &RenderText) {
+        /* Not a quad! */
+    }
+}
+// SAFETY: There's nothing to initialize.
+unsafe impl<Cx> Gather<Cx, RenderQuad> for RenderText {
+    fn size(&self) -> usize {
+        0
+    }
+
+    fn gather(&mut self, _: &mut Cx, _: &mut [MaybeUninit<RenderQuad>]) {}
+}
+impl From<&Text> for RenderText {
+    fn from(text: &Text) -> Self {
+        RenderText(text.rect, text.text.clone(), text.color)
     }
 }
 
@@ -55,7 +68,8 @@ pub fn Text() -> Text {
 }
 
 /// A simple coloured graphic.
-#[derive(Default, Clone, PartialEq)]
+#[derive(Default, Clone, PartialEq, Blueprint, Element, Bubble)]
+#[element(effect = RenderText)]
 pub struct Text {
     pub rect: Rect,
     pub text: String,
@@ -78,25 +92,3 @@ impl Text {
         Self { text, ..self }
     }
 }
-
-impl Bubble<Event, bool> for Text {
-    fn bubble(&mut self, _: &mut Event) -> bool {
-        Empty::empty()
-    }
-}
-
-impl Blueprint<TerminalEnvironment> for Text {
-    type Element = Self;
-
-    fn make(self, _: &TerminalBlueprintResources) -> Self::Element {
-        self
-    }
-}
-
-impl Element<TerminalEnvironment> for Text {
-    type Effect<'fx> = RenderText;
-
-    fn effect(&self) -> Self::Effect<'_> {
-        RenderText(self.rect, self.text.clone(), self.color)
-    }
-}
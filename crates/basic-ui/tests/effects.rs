@@ -0,0 +1,31 @@
+use ui_composer_basic_ui::primitives::{
+    graphic::{QuadBatch, RenderQuad},
+    text::RenderText,
+};
+use ui_composer_core::app::composition::visit::DriveThru as _;
+use ui_composer_math::prelude::{Point2, Rect, Size2, Srgba};
+
+#[test]
+fn quads_of_a_frame_are_batched_in_order() {
+    let quad = |x: f32| {
+        RenderQuad(
+            Rect::new(Point2::new(x, 0.0), Size2::new(1.0, 1.0)),
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        )
+    };
+    let text = RenderText(Rect::default(), "Hi!".into(), Srgba::default());
+
+    let mut frame = (
+        quad(0.0),
+        (Some(text), (vec![quad(1.0), quad(2.0)], None::<RenderQuad>)),
+    );
+    let expected = vec![quad(0.0), quad(1.0), quad(2.0)];
+
+    let mut batch = QuadBatch::new();
+    batch.gather(&mut frame);
+    assert_eq!(batch.quads, expected);
+
+    batch.clear();
+    frame.drive_thru(&mut batch);
+    assert_eq!(batch.quads, expected);
+}
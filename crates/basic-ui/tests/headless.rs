@@ -0,0 +1,515 @@
+use std::pin::Pin;
+use ui_composer_basic_ui::{
+    interaction::{Drag, DragState, Tap, Typing},
+    primitives::graphic::{Graphic, QuadBatch, RenderQuad},
+};
+use ui_composer_core::app::{
+    composition::algebra::Gather,
+    composition::effects::future::{IntoBlueprint as _, IntoTryBlueprint as _},
+    composition::effects::signal::IntoBlueprint as _,
+    composition::effects::signal_map::IntoBlueprint as _,
+    composition::effects::signal_vec::IntoBlueprint as _,
+    composition::effects::stream::{Accumulate as _, IntoBlueprint as _},
+    composition::elements::error_boundary::ErrorBoundary,
+    composition::elements::response::PointerCapture,
+    composition::elements::{Blueprint, Element},
+    composition::focus::{FocusScope, FocusScopeBlueprint},
+    composition::layout::{
+        ItemBox, LayoutItem as _,
+        hints::{CascadingContext, ParentHints},
+    },
+    composition::pointer::{PointerScopeBlueprint, Pointers},
+    runner::headless::{HeadlessEnvironment, HeadlessRunner},
+};
+use ui_composer_derive_ui::{Blueprint, Bubble, DriveThru, Element};
+use ui_composer_input::event::{
+    ButtonState, CursorEvent, DeviceId, Event, KeyEvent, KeyboardEvent,
+    Modifiers, MouseButton, NamedKey,
+};
+use ui_composer_math::{
+    flow::{CartesianFlow, CurrentFlow},
+    prelude::{Point2, Rect, Size2, Srgba, Vector2},
+};
+use ui_composer_state::{
+    State as _,
+    futures_signals::signal::{Mutable, SignalExt as _},
+    futures_signals::signal_map::MutableBTreeMap,
+    futures_signals::signal_vec::MutableVec,
+};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The quads `runner`'s app shows right now, batched like a renderer would.
+fn quads<App>(runner: &HeadlessRunner<App>) -> Vec<RenderQuad>
+where
+    App: Blueprint<HeadlessEnvironment>,
+    for<'fx> <App::Element as Element<HeadlessEnvironment>>::Effect<'fx>:
+        Gather<(), RenderQuad>,
+{
+    let mut batch = QuadBatch::new();
+    batch.gather_element(&*runner.element());
+    batch.quads
+}
+
+#[test]
+fn tapping_a_button_like_blueprint() {
+    let rect = Rect::new(Point2::new(0.0, 0.0), Size2::new(10.0, 3.0));
+    let idle_color = Srgba::new(0.0, 0.0, 1.0, 1.0);
+    let hovered_color = Srgba::new(1.0, 0.0, 0.0, 1.0);
+
+    let taps = Mutable::new(0);
+    let is_hovered = Mutable::new(false);
+
+    let blueprint = (
+        Tap::new(rect, taps.clone().effect(|t| *t += 1))
+            .with_hover_state(is_hovered.clone()),
+        is_hovered
+            .signal()
+            .map(move |hovered| {
+                Graphic::new(
+                    rect,
+                    if hovered { hovered_color } else { idle_color },
+                )
+            })
+            .into_blueprint(),
+    );
+
+    let mut runner = HeadlessRunner::new(blueprint);
+
+    assert_eq!(quads(&runner), vec![RenderQuad(rect, idle_color)]);
+
+    let _ = runner.send(Event::Cursor {
+        id: DeviceId(0),
+        event: CursorEvent::Moved {
+            position: Point2::new(1.0, 1.0),
+        },
+    });
+    assert_eq!(quads(&runner), vec![RenderQuad(rect, hovered_color)]);
+
+    let _ = runner.send(Event::Cursor {
+        id: DeviceId(0),
+        event: CursorEvent::Button(MouseButton::Left, ButtonState::Pressed),
+    });
+    assert_eq!(taps.get(), 1);
+}
+
+#[test]
+fn tab_moves_focus_between_text_inputs() {
+    let scope = FocusScope::new();
+    let first = Mutable::new(String::new());
+    let second = Mutable::new(String::new());
+
+    let blueprint = FocusScopeBlueprint::new(
+        scope.clone(),
+        (
+            Typing::new(first.clone()).with_focus(scope.focusable()),
+            Typing::new(second.clone()).with_focus(scope.focusable()),
+        ),
+    );
+    let mut runner = HeadlessRunner::new(blueprint);
+
+    let key =
+        |text: Option<&str>, named_key: Option<NamedKey>| Event::Keyboard {
+            id: DeviceId(0),
+            event: KeyboardEvent::Key(KeyEvent {
+                is_implicit: false,
+                text_repr: text.map(Into::into),
+                named_key,
+                button_state: ButtonState::Pressed,
+            }),
+        };
+    let tab = || key(None, Some(NamedKey::Tab));
+    let shift = |shift| Event::Keyboard {
+        id: DeviceId(0),
+        event: KeyboardEvent::ModifiersChanged(Modifiers {
+            shift,
+            ..Modifiers::default()
+        }),
+    };
+
+    // Nothing is focused yet, so nobody types.
+    let _ = runner.send(key(Some("x"), None));
+    assert_eq!(
+        (first.get_cloned(), second.get_cloned()),
+        ("".into(), "".into())
+    );
+
+    runner.send_all([tab(), key(Some("a"), None)]);
+    runner.send_all([tab(), key(Some("b"), None)]);
+    assert_eq!(
+        (first.get_cloned(), second.get_cloned()),
+        ("a".into(), "b".into())
+    );
+
+    runner.send_all([shift(true), tab(), shift(false), key(Some("c"), None)]);
+    assert_eq!(
+        (first.get_cloned(), second.get_cloned()),
+        ("ac".into(), "b".into())
+    );
+}
+
+#[test]
+fn focus_order_follows_rects_and_forgets_dropped_items() {
+    let scope = FocusScope::new();
+    let line = |y| Rect::new(Point2::new(0.0, y), Size2::new(10.0, 1.0));
+
+    let below = scope.focusable();
+    let above = scope.focusable();
+    let (below_id, above_id) = (below.id(), above.id());
+    let inputs = (
+        Typing::new(Mutable::default())
+            .with_focus(below)
+            .with_rect(line(1.0)),
+        Typing::new(Mutable::default())
+            .with_rect(line(0.0))
+            .with_focus(above),
+    );
+    assert_eq!(scope.order(), [above_id, below_id]);
+
+    scope.focus(below_id);
+    drop(inputs);
+    assert_eq!(scope.order(), []);
+    assert_eq!(scope.focused(), None);
+}
+
+#[test]
+fn dragging_continues_outside_the_rect_while_captured() {
+    let pointers = Pointers::new();
+    let handle = Rect::new(Point2::new(0.0, 0.0), Size2::new(2.0, 2.0));
+    let button = Rect::new(Point2::new(5.0, 0.0), Size2::new(2.0, 2.0));
+
+    let displacement = Mutable::new(Vector2::new(0.0, 0.0));
+    let is_button_hovered = Mutable::new(false);
+
+    let blueprint = PointerScopeBlueprint::new(
+        pointers.clone(),
+        (
+            Drag::new(
+                handle,
+                Mutable::new(DragState::None),
+                Mutable::new(Point2::new(0.0, 0.0)),
+                displacement.clone(),
+            )
+            .with_pointers(pointers.clone()),
+            Tap::new(button, || {})
+                .with_hover_state(is_button_hovered.clone())
+                .with_pointers(pointers.clone()),
+        ),
+    );
+    let mut runner = HeadlessRunner::new(blueprint);
+
+    let moved = |x, y| Event::Cursor {
+        id: DeviceId(0),
+        event: CursorEvent::Moved {
+            position: Point2::new(x, y),
+        },
+    };
+    let left = |state| Event::Cursor {
+        id: DeviceId(0),
+        event: CursorEvent::Button(MouseButton::Left, state),
+    };
+
+    let _ = runner.send(moved(1.0, 1.0));
+    let response = runner.send(left(ButtonState::Pressed));
+    assert_eq!(
+        response.pointer_capture,
+        Some(PointerCapture::Capture(DeviceId(0)))
+    );
+
+    // The cursor is over the button now, but the drag has the pointer.
+    let _ = runner.send(moved(6.0, 1.0));
+    assert_eq!(displacement.get(), Vector2::new(5.0, 0.0));
+    assert!(!is_button_hovered.get());
+
+    let response = runner.send(left(ButtonState::Released));
+    assert_eq!(
+        response.pointer_capture,
+        Some(PointerCapture::Release(DeviceId(0)))
+    );
+
+    let _ = runner.send(moved(6.0, 1.5));
+    assert!(is_button_hovered.get());
+}
+
+#[test]
+fn error_boundaries_show_their_fallback_when_content_fails() {
+    let rect = Rect::new(Point2::new(0.0, 0.0), Size2::new(10.0, 3.0));
+    let content_color = Srgba::new(0.0, 0.0, 1.0, 1.0);
+    let fallback_color = Srgba::new(1.0, 0.0, 0.0, 1.0);
+
+    let boundary = |result: Result<(), &'static str>| {
+        ErrorBoundary::new(
+            std::future::ready(
+                result.map(|()| Graphic::new(rect, content_color)),
+            )
+            .into_try_blueprint(),
+            Graphic::new(rect, fallback_color),
+        )
+    };
+    let runner = HeadlessRunner::new(boundary(Ok(())));
+    assert_eq!(
+        runner.effects_of::<RenderQuad>(),
+        vec![RenderQuad(rect, content_color)]
+    );
+
+    let error_state = Mutable::new(None);
+    let runner = HeadlessRunner::new(
+        boundary(Err("Oh no!")).with_error_state(error_state.clone()),
+    );
+    assert_eq!(
+        runner.effects_of::<RenderQuad>(),
+        vec![RenderQuad(rect, fallback_color)]
+    );
+    assert_eq!(
+        error_state
+            .lock_ref()
+            .as_ref()
+            .map(|caught| caught.to_string()),
+        Some("Oh no!".to_string())
+    );
+}
+
+#[test]
+fn suspense_shows_its_placeholder_until_resolved_or_timed_out() {
+    let rect = Rect::new(Point2::new(0.0, 0.0), Size2::new(10.0, 3.0));
+    let content = Graphic::new(rect, Srgba::new(0.0, 0.0, 1.0, 1.0));
+    let placeholder = Graphic::new(rect, Srgba::new(0.5, 0.5, 0.5, 1.0));
+    let timeout_view = Graphic::new(rect, Srgba::new(1.0, 0.0, 0.0, 1.0));
+    let waiting = HeadlessRunner::new(
+        std::future::pending::<Graphic>()
+            .into_blueprint()
+            .with_fallback(placeholder),
+    );
+    assert_eq!(
+        waiting.effects_of::<RenderQuad>(),
+        vec![RenderQuad::from(&placeholder)]
+    );
+
+    let resolved = HeadlessRunner::new(
+        std::future::ready(content)
+            .into_blueprint()
+            .with_fallback(placeholder),
+    );
+    assert_eq!(
+        resolved.effects_of::<RenderQuad>(),
+        vec![RenderQuad::from(&content)]
+    );
+
+    let timed_out = HeadlessRunner::new(
+        std::future::pending::<Graphic>()
+            .into_blueprint()
+            .with_fallback(placeholder)
+            .with_timeout(std::future::ready(()), timeout_view),
+    );
+    assert_eq!(
+        timed_out.effects_of::<RenderQuad>(),
+        vec![RenderQuad::from(&timeout_view)]
+    );
+}
+
+#[test]
+fn accumulated_streams_show_every_item() {
+    let line = |y: f32| {
+        Graphic::new(
+            Rect::new(Point2::new(0.0, y), Size2::new(10.0, 1.0)),
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        )
+    };
+
+    let log = futures::stream::iter([0.0, 1.0, 2.0]).accumulate(
+        Vec::new(),
+        move |log, y| {
+            log.push(line(y));
+            log.clone()
+        },
+    );
+    let runner = HeadlessRunner::new(log.into_blueprint());
+    assert_eq!(
+        runner.frames().iter().map(Vec::len).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+
+    // A stream that's always ready doesn't keep the runner busy forever.
+    let endless = HeadlessRunner::new(
+        futures::stream::repeat(line(0.0)).into_blueprint(),
+    );
+    assert!(!endless.frames().is_empty());
+}
+
+#[test]
+fn keyed_maps_follow_their_entries() {
+    let line = |y: f32| {
+        Graphic::new(
+            Rect::new(Point2::new(0.0, y), Size2::new(10.0, 1.0)),
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        )
+    };
+    let lines =
+        MutableBTreeMap::with_values([(1, line(1.0)), (2, line(2.0))].into());
+    let mut runner =
+        HeadlessRunner::new(lines.signal_map_cloned().into_blueprint());
+    let quads_at = |ys: &[f32]| {
+        ys.iter()
+            .map(|&y| RenderQuad::from(&line(y)))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(runner.effects_of::<RenderQuad>(), quads_at(&[1.0, 2.0]));
+
+    {
+        let mut lines = lines.lock_mut();
+        lines.insert_cloned(0, line(0.0));
+        lines.insert_cloned(1, line(1.5));
+        lines.remove(&2);
+    }
+    runner.settle();
+    assert_eq!(runner.effects_of::<RenderQuad>(), quads_at(&[0.0, 1.5]));
+}
+
+#[test]
+fn relayouts_keep_the_state_of_their_elements() {
+    let width = Mutable::new(3.0);
+    let is_hovered = Mutable::new(false);
+    let placements = Arc::new(AtomicUsize::new(0));
+
+    let mut item = ItemBox::new({
+        let (width, is_hovered) = (width.clone(), is_hovered.clone());
+        let placements = placements.clone();
+        move |hx: ParentHints| {
+            placements.fetch_add(1, Ordering::Relaxed);
+            let size = Size2::new(width.get(), 1.0);
+            Tap::new(Rect::new(hx.rect.origin, size), || {})
+                .with_hover_state(is_hovered.clone())
+        }
+    })
+    .relayout_on({
+        let width = width.clone();
+        move || width.signal().map(|_| ())
+    });
+    let mut runner = HeadlessRunner::new(item.place(ParentHints {
+        rect: Rect::new(Point2::ZERO, Size2::new(10.0, 1.0)),
+        current_flow: CurrentFlow {
+            current_flow_direction: CartesianFlow::LeftToRight,
+            current_cross_flow_direction: CartesianFlow::TopToBottom,
+            current_writing_flow_direction: CartesianFlow::LeftToRight,
+            current_writing_cross_flow_direction: CartesianFlow::TopToBottom,
+        },
+        context: CascadingContext::default(),
+    }));
+
+    // The item was just laid out, so nothing changed yet.
+    runner.settle();
+    assert_eq!(placements.load(Ordering::Relaxed), 1);
+
+    let _ = runner.send(Event::Cursor {
+        id: DeviceId(0),
+        event: CursorEvent::Moved {
+            position: Point2::new(5.0, 0.5),
+        },
+    });
+    assert!(!is_hovered.get());
+
+    // The tap grows under the cursor, which it remembers from before.
+    width.set(10.0);
+    runner.settle();
+    assert_eq!(placements.load(Ordering::Relaxed), 2);
+    assert!(is_hovered.get());
+}
+
+/// A leaf that remembers what it was made as, even after it is reconciled.
+#[derive(Clone, Blueprint, Bubble, Element)]
+#[element(effect = Seen, reconcile = Self::patch)]
+struct Probe {
+    key: u32,
+    label: &'static str,
+    made_as: &'static str,
+}
+
+impl Probe {
+    fn new(key: u32, label: &'static str) -> Self {
+        Self {
+            key,
+            label,
+            made_as: label,
+        }
+    }
+
+    fn patch(self, element: Pin<&mut Self>) {
+        let element = element.get_mut();
+        element.key = self.key;
+        element.label = self.label;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, DriveThru)]
+#[drive_thru(visit)]
+struct Seen(&'static str, &'static str);
+
+impl From<&Probe> for Seen {
+    fn from(probe: &Probe) -> Self {
+        Seen(probe.label, probe.made_as)
+    }
+}
+
+#[test]
+fn keyed_vecs_keep_the_state_of_their_elements() {
+    let probes = MutableVec::new_with_values(vec![
+        Probe::new(1, "a"),
+        Probe::new(2, "b"),
+        Probe::new(3, "c"),
+    ]);
+    let mut runner = HeadlessRunner::new(
+        probes
+            .signal_vec_cloned()
+            .into_blueprint()
+            .with_key(|probe: &Probe| probe.key),
+    );
+    let seen = |runner: &mut HeadlessRunner<_>| {
+        runner.settle();
+        runner.effects_of::<Seen>()
+    };
+    assert_eq!(
+        seen(&mut runner),
+        [Seen("a", "a"), Seen("b", "b"), Seen("c", "c")]
+    );
+
+    // Inserting and moving leave the other elements alone.
+    probes.lock_mut().insert_cloned(0, Probe::new(4, "d"));
+    probes.lock_mut().move_from_to(0, 3);
+    assert_eq!(
+        seen(&mut runner),
+        [
+            Seen("a", "a"),
+            Seen("b", "b"),
+            Seen("c", "c"),
+            Seen("d", "d")
+        ]
+    );
+
+    // Updating an item with the same key reconciles its element...
+    probes.lock_mut().set_cloned(0, Probe::new(1, "a2"));
+    // ...but one with another key is made anew.
+    probes.lock_mut().set_cloned(1, Probe::new(5, "e"));
+    assert_eq!(
+        seen(&mut runner),
+        [
+            Seen("a2", "a"),
+            Seen("e", "e"),
+            Seen("c", "c"),
+            Seen("d", "d")
+        ]
+    );
+
+    probes.lock_mut().remove(1);
+    probes.lock_mut().pop();
+    assert_eq!(seen(&mut runner), [Seen("a2", "a"), Seen("c", "c")]);
+
+    // Replacing the whole list reuses the elements of matching keys.
+    probes
+        .lock_mut()
+        .replace_cloned(vec![Probe::new(3, "c2"), Probe::new(6, "f")]);
+    assert_eq!(seen(&mut runner), [Seen("c2", "c"), Seen("f", "f")]);
+
+    probes.lock_mut().clear();
+    assert_eq!(seen(&mut runner), []);
+}
@@ -0,0 +1,215 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use ui_composer_basic_ui::components::ColorBox;
+use ui_composer_basic_ui::layout::{
+    MonospaceText, cascade, column, flex, for_each, item, linewise_flow, row,
+    stacked,
+};
+use ui_composer_core::app::composition::layout::{
+    ItemBox, LayoutItem,
+    hints::{CascadingContext, ChildHints, ParentHints},
+    memo::Invalidator,
+};
+use ui_composer_math::{
+    flow::{CartesianFlow, CurrentFlow},
+    prelude::{Point2, Rect, Size2, Srgba},
+};
+use ui_composer_state::futures_signals::signal_vec::MutableVec;
+
+fn parent_hints(size: Size2) -> ParentHints {
+    ParentHints {
+        rect: Rect::new(Point2::ZERO, size),
+        current_flow: CurrentFlow {
+            current_flow_direction: CartesianFlow::LeftToRight,
+            current_cross_flow_direction: CartesianFlow::TopToBottom,
+            current_writing_flow_direction: CartesianFlow::LeftToRight,
+            current_writing_cross_flow_direction: CartesianFlow::TopToBottom,
+        },
+        context: CascadingContext::default(),
+    }
+}
+
+#[test]
+fn wrapped_text_in_a_column_is_as_tall_as_its_lines() {
+    let text = || {
+        linewise_flow(MonospaceText(
+            "aaaa bbbb cccc".into(),
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        ))
+    };
+    let hints = parent_hints(Size2::new(20.0, 10.0));
+
+    // Squeezed to its minimum width, each word gets its own line...
+    assert_eq!(
+        text().prepare(hints.clone()).minimum_size,
+        Size2::new(4.0, 3.0)
+    );
+    assert_eq!(text().height_for_width(4.0, hints.clone()), 3.0);
+    // ...but with room to spare, they all fit in one.
+    assert_eq!(text().height_for_width(20.0, hints.clone()), 1.0);
+
+    let mut stack = column([text(), text()]);
+    assert_eq!(
+        stack.prepare(hints.clone()).minimum_size,
+        Size2::new(4.0, 2.0)
+    );
+    assert_eq!(stack.height_for_width(9.0, hints.clone()), 4.0);
+}
+
+/// An item that counts how many times it's prepared and measured.
+struct Counted {
+    prepares: Arc<AtomicUsize>,
+    measures: Arc<AtomicUsize>,
+}
+
+impl LayoutItem for Counted {
+    type Blueprint = ();
+
+    fn prepare(&mut self, _: ParentHints) -> ChildHints {
+        self.prepares.fetch_add(1, Ordering::Relaxed);
+        ChildHints::new(Size2::new(1.0, 1.0))
+    }
+
+    fn place(&mut self, _: ParentHints) -> Self::Blueprint {}
+
+    fn height_for_width(&mut self, _: f32, _: ParentHints) -> f32 {
+        self.measures.fetch_add(1, Ordering::Relaxed);
+        1.0
+    }
+}
+
+#[test]
+fn flex_prepares_and_measures_each_item_once_per_pass() {
+    let prepares = Arc::new(AtomicUsize::new(0));
+    let measures = Arc::new(AtomicUsize::new(0));
+    let counted = || {
+        item(Counted {
+            prepares: prepares.clone(),
+            measures: measures.clone(),
+        })
+        .with_grow(1.0)
+    };
+    let hints = parent_hints(Size2::new(20.0, 10.0));
+
+    for mut container in [
+        flex((counted(), counted())),
+        flex((counted(), counted())).with_vertical_flow(),
+    ] {
+        prepares.store(0, Ordering::Relaxed);
+        measures.store(0, Ordering::Relaxed);
+        container.prepare(hints.clone());
+        container.place(hints.clone());
+        assert_eq!(prepares.load(Ordering::Relaxed), 2);
+        assert_eq!(measures.load(Ordering::Relaxed), 2);
+    }
+
+    // Wrapped text splits the width, and the container is as tall as its tallest item.
+    let text = || {
+        item(linewise_flow(MonospaceText(
+            "aaaa bbbb cccc".into(),
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        )))
+        .with_grow(1.0)
+    };
+    let mut container = flex((text(), text()));
+    container.prepare(hints.clone());
+    assert_eq!(container.height_for_width(8.0, hints.clone()), 3.0);
+    assert_eq!(container.height_for_width(40.0, hints.clone()), 1.0);
+}
+
+#[test]
+fn stacks_take_any_number_of_items() {
+    let square = |side: f32| ColorBox().with_size(Size2::new(side, side));
+    let hints = parent_hints(Size2::new(20.0, 20.0));
+
+    let mut stack = row((
+        stacked(square(1.0)),
+        (stacked(square(2.0)), vec![square(3.0), square(4.0)]),
+    ))
+    .with_gap(1.0);
+    assert_eq!(
+        stack.prepare(hints.clone()).minimum_size,
+        Size2::new(13.0, 4.0)
+    );
+
+    let mut stack =
+        column([square(1.0), square(2.0), square(3.0)]).with_gap(2.0);
+    assert_eq!(
+        stack.prepare(hints.clone()).minimum_size,
+        Size2::new(3.0, 10.0)
+    );
+}
+
+#[test]
+fn dynamic_stacks_follow_their_vec() {
+    let square = |side: f32| ColorBox().with_size(Size2::new(side, side));
+    let hints = parent_hints(Size2::new(20.0, 20.0));
+
+    let sides = MutableVec::new_with_values(vec![1.0, 2.0]);
+    let mut stack = column(for_each(sides.clone(), square));
+    assert_eq!(
+        stack.prepare(hints.clone()).minimum_size,
+        Size2::new(2.0, 3.0)
+    );
+
+    sides.lock_mut().push(3.0);
+    assert_eq!(
+        stack.prepare(hints.clone()).minimum_size,
+        Size2::new(3.0, 6.0)
+    );
+    assert_eq!(stack.place(hints.clone()).0.len(), 3);
+}
+
+#[test]
+fn memoized_items_are_only_laid_out_again_when_needed() {
+    let placements = Arc::new(AtomicUsize::new(0));
+    let invalidator = Invalidator::new();
+
+    let counter = placements.clone();
+    let mut item = ItemBox::new(move |_| {
+        counter.fetch_add(1, Ordering::Relaxed);
+    })
+    .memo()
+    .with_invalidator(invalidator.clone());
+
+    let small = parent_hints(Size2::new(10.0, 10.0));
+    let big = parent_hints(Size2::new(20.0, 20.0));
+
+    item.place(small.clone());
+    item.place(small);
+    assert_eq!(placements.load(Ordering::Relaxed), 1);
+
+    item.place(big.clone());
+    assert_eq!(placements.load(Ordering::Relaxed), 2);
+
+    invalidator.invalidate();
+    item.place(big);
+    assert_eq!(placements.load(Ordering::Relaxed), 3);
+}
+
+#[derive(Debug, PartialEq)]
+struct Accent(Srgba);
+
+#[test]
+fn apps_can_cascade_their_own_values() {
+    let accent = Srgba::new(0.7, 0.35, 0.06, 1.0);
+    let seen = Arc::new(Mutex::new(None));
+
+    let mut item = cascade(
+        move |cx| cx.with_value(Accent(accent)),
+        ItemBox::new({
+            let seen = seen.clone();
+            move |hx| {
+                *seen.lock().unwrap() =
+                    hx.context.value::<Accent>().map(|accent| accent.0);
+            }
+        }),
+    );
+    item.place(parent_hints(Size2::new(10.0, 10.0)));
+    assert_eq!(*seen.lock().unwrap(), Some(accent));
+
+    // Contexts with equal values are equal, so memoized items don't lay out again.
+    let context = || CascadingContext::default().with_value(Accent(accent));
+    assert_eq!(context(), context());
+    assert_ne!(context(), CascadingContext::default());
+}
@@ -1,4 +1,6 @@
-use crate::app::composition::algebra::{Bubble, Empty, Gather, Semigroup};
+use crate::app::composition::algebra::{
+    Bubble, Empty, Gather, Propagate, Semigroup,
+};
 
 /* Combine */
 
@@ -14,6 +16,12 @@ impl Empty for bool {
     }
 }
 
+impl Propagate for bool {
+    fn stops_propagation(&self) -> bool {
+        false
+    }
+}
+
 impl Semigroup for std::task::Poll<Option<()>> {
     fn combine(self, other: Self) -> Self {
         use std::task::Poll::*;
@@ -33,7 +41,7 @@ impl Empty for std::task::Poll<Option<()>> {
 }
 
 pub mod bubble {
-    use super::{Bubble, Empty, Semigroup};
+    use super::{Bubble, Empty, Propagate, Semigroup};
     use crate::app::composition::algebra::Monoid;
 
     impl<Down, Up> Bubble<Down, Up> for ()
@@ -60,10 +68,13 @@ pub mod bubble {
     where
         A: Bubble<Down, Up>,
         B: Bubble<Down, Up>,
-        Up: Semigroup,
+        Up: Semigroup + Propagate,
     {
         fn bubble(&mut self, cx: &mut Down) -> Up {
             let a = self.0.bubble(cx);
+            if a.stops_propagation() {
+                return a;
+            }
             let b = self.1.bubble(cx);
             a.combine(b)
         }
@@ -72,27 +83,42 @@ pub mod bubble {
     impl<A, Down, Up, const N: usize> Bubble<Down, Up> for [A; N]
     where
         A: Bubble<Down, Up>,
-        Up: Monoid,
+        Up: Monoid + Propagate,
     {
         fn bubble(&mut self, cx: &mut Down) -> Up {
-            self.iter_mut().fold(Empty::empty(), |acc, el| {
-                Semigroup::combine(acc, el.bubble(cx))
-            })
+            bubble_each(self.iter_mut(), cx)
         }
     }
 
     impl<A, Down, Up> Bubble<Down, Up> for Vec<A>
     where
         A: Bubble<Down, Up>,
-        Up: Monoid,
+        Up: Monoid + Propagate,
     {
         fn bubble(&mut self, cx: &mut Down) -> Up {
-            self.iter_mut().fold(Empty::empty(), |acc, el| {
-                Semigroup::combine(acc, el.bubble(cx))
-            })
+            bubble_each(self.iter_mut(), cx)
         }
     }
 
+    /// Bubbles down each item in order, stopping early if one of them asks to.
+    pub(crate) fn bubble_each<'a, A, Down, Up>(
+        items: impl Iterator<Item = &'a mut A>,
+        cx: &mut Down,
+    ) -> Up
+    where
+        A: Bubble<Down, Up> + 'a,
+        Up: Monoid + Propagate,
+    {
+        let mut acc = Up::empty();
+        for item in items {
+            acc = acc.combine(item.bubble(cx));
+            if acc.stops_propagation() {
+                break;
+            }
+        }
+        acc
+    }
+
     impl<A, Down, Up> Bubble<Down, Up> for Option<A>
     where
         A: Bubble<Down, Up>,
@@ -125,8 +151,11 @@ pub mod gather {
     use super::Gather;
     use std::mem::MaybeUninit;
 
-    impl<Context, Item> Gather<Context, Item> for () {
-        const SIZE: usize = 0;
+    // SAFETY: There's nothing to initialize.
+    unsafe impl<Context, Item> Gather<Context, Item> for () {
+        fn size(&self) -> usize {
+            0
+        }
 
         fn gather(
             &mut self,
@@ -137,80 +166,121 @@ pub mod gather {
         }
     }
 
-    impl<A, B, Context, Item> Gather<Context, Item> for (A, B)
+    // SAFETY: Each half of the buffer is initialized by one of the items.
+    unsafe impl<A, B, Context, Item> Gather<Context, Item> for (A, B)
     where
         A: Gather<Context, Item>,
         B: Gather<Context, Item>,
     {
-        const SIZE: usize = A::SIZE + B::SIZE;
+        fn size(&self) -> usize {
+            self.0.size() + self.1.size()
+        }
 
         fn gather(&mut self, cx: &mut Context, acc: &mut [MaybeUninit<Item>]) {
-            debug_assert_eq!(acc.len(), Self::SIZE);
-            let (left, right) = acc.split_at_mut(A::SIZE);
+            debug_assert_eq!(acc.len(), self.size());
+            let (left, right) = acc.split_at_mut(self.0.size());
             self.0.gather(cx, left);
             self.1.gather(cx, right);
         }
     }
 
-    impl<A, Context, Item, const N: usize> Gather<Context, Item> for [A; N]
+    /// Gathers each item of a list into consecutive parts of the buffer.
+    fn gather_each<'a, A, Context, Item>(
+        items: impl Iterator<Item = &'a mut A>,
+        cx: &mut Context,
+        mut acc: &mut [MaybeUninit<Item>],
+    ) where
+        A: Gather<Context, Item> + 'a,
+    {
+        for item in items {
+            let (head, tail) =
+                std::mem::take(&mut acc).split_at_mut(item.size());
+            item.gather(cx, head);
+            acc = tail;
+        }
+        debug_assert!(acc.is_empty());
+    }
+
+    // SAFETY: Consecutive parts of the buffer are initialized by each item.
+    unsafe impl<A, Context, Item, const N: usize> Gather<Context, Item> for [A; N]
     where
         A: Gather<Context, Item>,
     {
-        const SIZE: usize = A::SIZE * N;
+        fn size(&self) -> usize {
+            self.iter().map(|item| item.size()).sum()
+        }
 
         fn gather(&mut self, cx: &mut Context, acc: &mut [MaybeUninit<Item>]) {
-            debug_assert_eq!(acc.len(), Self::SIZE);
+            debug_assert_eq!(acc.len(), self.size());
+            gather_each(self.iter_mut(), cx, acc);
+        }
+    }
 
-            for (i, elem) in self.iter_mut().enumerate() {
-                let start = i * A::SIZE;
-                let end = start + A::SIZE;
-                elem.gather(cx, &mut acc[start..end]);
-            }
+    // SAFETY: Consecutive parts of the buffer are initialized by each item.
+    unsafe impl<A, Context, Item> Gather<Context, Item> for Vec<A>
+    where
+        A: Gather<Context, Item>,
+    {
+        fn size(&self) -> usize {
+            self.iter().map(|item| item.size()).sum()
+        }
+
+        fn gather(&mut self, cx: &mut Context, acc: &mut [MaybeUninit<Item>]) {
+            debug_assert_eq!(acc.len(), self.size());
+            gather_each(self.iter_mut(), cx, acc);
         }
     }
 
-    impl<A, Context, Item> Gather<Context, Item> for Option<A>
+    // SAFETY: `None` gathers nothing, `Some` is initialized by its content.
+    unsafe impl<A, Context, Item> Gather<Context, Item> for Option<A>
     where
         A: Gather<Context, Item>,
     {
-        const SIZE: usize = A::SIZE;
+        fn size(&self) -> usize {
+            self.as_ref().map_or(0, |inner| inner.size())
+        }
 
         fn gather(&mut self, cx: &mut Context, acc: &mut [MaybeUninit<Item>]) {
-            debug_assert_eq!(acc.len(), Self::SIZE);
-            match self {
-                None => { /* Leave buffer initialized. */ }
-                Some(inner) => inner.gather(cx, acc),
+            debug_assert_eq!(acc.len(), self.size());
+            if let Some(inner) = self {
+                inner.gather(cx, acc);
             }
         }
     }
 
-    impl<T, E, Context, Item> Gather<Context, Item> for Result<T, E>
+    // SAFETY: The buffer is initialized by whichever variant is there.
+    unsafe impl<T, E, Context, Item> Gather<Context, Item> for Result<T, E>
     where
         T: Gather<Context, Item>,
         E: Gather<Context, Item>,
     {
-        const SIZE: usize = if T::SIZE > E::SIZE { T::SIZE } else { E::SIZE };
+        fn size(&self) -> usize {
+            match self {
+                Err(inner) => inner.size(),
+                Ok(inner) => inner.size(),
+            }
+        }
 
         fn gather(&mut self, cx: &mut Context, acc: &mut [MaybeUninit<Item>]) {
-            debug_assert_eq!(acc.len(), Self::SIZE);
-
-            /* Assuming space that isn't written to is initialized! */
-
+            debug_assert_eq!(acc.len(), self.size());
             match self {
-                Err(inner) => inner.gather(cx, &mut acc[..E::SIZE]),
-                Ok(inner) => inner.gather(cx, &mut acc[..T::SIZE]),
+                Err(inner) => inner.gather(cx, acc),
+                Ok(inner) => inner.gather(cx, acc),
             }
         }
     }
 
-    impl<A, Context, Item> Gather<Context, Item> for Box<A>
+    // SAFETY: The buffer is initialized by the boxed item.
+    unsafe impl<A, Context, Item> Gather<Context, Item> for Box<A>
     where
-        A: Gather<Context, Item>,
+        A: Gather<Context, Item> + ?Sized,
     {
-        const SIZE: usize = A::SIZE;
+        fn size(&self) -> usize {
+            self.as_ref().size()
+        }
 
         fn gather(&mut self, cx: &mut Context, acc: &mut [MaybeUninit<Item>]) {
-            debug_assert_eq!(acc.len(), Self::SIZE);
+            debug_assert_eq!(acc.len(), self.size());
             self.as_mut().gather(cx, acc);
         }
     }
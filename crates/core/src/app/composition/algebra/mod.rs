@@ -20,6 +20,14 @@ pub trait Empty {
 pub trait Monoid: Semigroup + Empty {}
 impl<T> Monoid for T where T: Semigroup + Empty {}
 
+/// Trait for a value bubbled up by a structure, which might ask
+/// for the rest of that structure to be skipped.
+pub trait Propagate {
+    /// Whether the parts of the structure that weren't visited yet
+    /// should not see the value being bubbled down.
+    fn stops_propagation(&self) -> bool;
+}
+
 /// Type for something that can bubble a value down its structure (anamorphism)
 /// and bubble up a response (catamorphism).
 pub trait Bubble<Down, Up> {
@@ -28,14 +36,33 @@ pub trait Bubble<Down, Up> {
 
 /// Like [`Bubble`] but combines the result into a cartesian product
 /// (a buffer) instead of using monoids.
-pub trait Gather<Context, Item> {
-    const SIZE: usize;
+///
+/// Structures first tell how many items they'll gather, so that the buffer
+/// can be allocated once, and then write their items to it in order.
+///
+/// # Safety
+///
+/// `gather` must initialize every item of `acc`,
+/// which is exactly [`Gather::size`] items long.
+pub unsafe trait Gather<Context, Item> {
+    /// How many items [`Gather::gather`] will write.
+    fn size(&self) -> usize;
 
     fn gather(
         &mut self,
         cx: &mut Context,
         acc: &mut [std::mem::MaybeUninit<Item>],
     );
+
+    /// Gathers all items at the end of `buffer`.
+    fn gather_into(&mut self, cx: &mut Context, buffer: &mut Vec<Item>) {
+        let size = self.size();
+        let len = buffer.len();
+        buffer.reserve(size);
+        self.gather(cx, &mut buffer.spare_capacity_mut()[..size]);
+        // SAFETY: The `size` items after `len` were initialized by `gather`.
+        unsafe { buffer.set_len(len + size) };
+    }
 }
 
 // #[cfg(feature = "specialization")]
@@ -1,10 +1,11 @@
-use crate::app::composition::algebra::Bubble;
+use crate::app::composition::algebra::{Bubble, Semigroup as _};
 use crate::app::composition::elements::Environment;
+use crate::app::composition::elements::response::EventResponse;
 use ui_composer_input::event::Event;
 
-use super::super::elements::{Blueprint, Element};
+use super::super::elements::{Blueprint, Element, TryBlueprint};
 use pin_project::pin_project;
-use std::future::Future;
+use std::future::{Future, Pending};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -31,11 +32,11 @@ where
     }
 }
 
-impl<Fut, Env: Environment> Bubble<Event, bool> for ReactOnce<Fut, Env>
+impl<Fut, Env: Environment> Bubble<Event, EventResponse> for ReactOnce<Fut, Env>
 where
     Fut: Future<Output: Blueprint<Env>>,
 {
-    fn bubble(&mut self, cx: &mut Event) -> bool {
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
         self.element
             .as_mut()
             .map(|e| e.bubble(cx))
@@ -78,12 +79,12 @@ where
         // SAFETY: Because the future is pinned in this struct, its captures are stable.
         match this.future.poll(cx) {
             Poll::Ready(blueprint) => {
-                let mut element = blueprint.make(env);
+                let element = this.element.insert(blueprint.make(env));
 
                 // Wake up the element.
-                let _ =
-                    unsafe { Pin::new_unchecked(&mut element) }.poll(cx, env);
-                *this.element = Some(element);
+                // SAFETY: The element is never moved out of this struct,
+                // as if it was pinned in it.
+                let _ = unsafe { Pin::new_unchecked(element) }.poll(cx, env);
 
                 Poll::Ready(Some(()))
             }
@@ -120,3 +121,357 @@ where
         }
     }
 }
+
+impl<Fut, Env: Environment> ReactOnce<Fut, Env>
+where
+    Fut: Future<Output: Blueprint<Env>>,
+{
+    /// Shows `placeholder` (say, a spinner) until the future resolves.
+    ///
+    /// ```rust,ignore
+    /// fetch_text()
+    ///     .map(|text| Text { rect, text, color })
+    ///     .into_blueprint()
+    ///     .with_fallback(Spinner(rect))
+    /// ```
+    pub fn with_fallback<P>(
+        self,
+        placeholder: P,
+    ) -> Suspense<Fut, P, Pending<()>, (), Env>
+    where
+        P: Blueprint<Env>,
+    {
+        Suspense {
+            content: self,
+            placeholder,
+            timeout: std::future::pending(),
+            timeout_view: (),
+        }
+    }
+}
+
+/// A [`ReactOnce`] that shows a placeholder until its future resolves,
+/// made with [`ReactOnce::with_fallback`].
+///
+/// It can also give up on the future after a timeout, see [`Suspense::with_timeout`].
+#[must_use = "Suspense does nothing unless made into an element"]
+pub struct Suspense<Fut, P, T, X, Env: Environment>
+where
+    Fut: Future<Output: Blueprint<Env>>,
+{
+    content: ReactOnce<Fut, Env>,
+    placeholder: P,
+    timeout: T,
+    timeout_view: X,
+}
+
+impl<Fut, P, T, X, Env: Environment> Suspense<Fut, P, T, X, Env>
+where
+    Fut: Future<Output: Blueprint<Env>>,
+{
+    /// Gives up on the future once `timeout` resolves, showing `timeout_view` instead.
+    ///
+    /// Any future will do as `timeout` — usually a timer from your async runtime.
+    pub fn with_timeout<T2, X2>(
+        self,
+        timeout: T2,
+        timeout_view: X2,
+    ) -> Suspense<Fut, P, T2, X2, Env>
+    where
+        T2: Future,
+        X2: Blueprint<Env>,
+    {
+        Suspense {
+            content: self.content,
+            placeholder: self.placeholder,
+            timeout,
+            timeout_view,
+        }
+    }
+}
+
+impl<Fut, P, T, X, Env: Environment> Blueprint<Env>
+    for Suspense<Fut, P, T, X, Env>
+where
+    Fut: Future<Output: Blueprint<Env>>,
+    P: Blueprint<Env>,
+    T: Future,
+    X: Blueprint<Env>,
+{
+    type Element = SuspenseElement<Fut, P, T, X, Env>;
+
+    fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
+        SuspenseElement {
+            content: Some(self.content),
+            placeholder: Some(self.placeholder.make(env)),
+            timeout: Some(self.timeout),
+            timeout_view: Some(self.timeout_view),
+            timed_out: None,
+            placeholder_shown: false,
+        }
+    }
+}
+
+/// The element made by a [`Suspense`].
+#[pin_project]
+pub struct SuspenseElement<Fut, P, T, X, Env: Environment>
+where
+    Fut: Future<Output: Blueprint<Env>>,
+    P: Blueprint<Env>,
+    X: Blueprint<Env>,
+{
+    /// The content, until the timeout resolves.
+    #[pin]
+    content: Option<ReactOnce<Fut, Env>>,
+    /// The placeholder, until the content or the timeout resolves.
+    #[pin]
+    placeholder: Option<P::Element>,
+    #[pin]
+    timeout: Option<T>,
+    timeout_view: Option<X>,
+    #[pin]
+    timed_out: Option<X::Element>,
+    /// Whether the runner was told about the placeholder,
+    /// which happens on the first poll after it's made.
+    placeholder_shown: bool,
+}
+
+impl<Fut, P, T, X, Env: Environment> Bubble<Event, EventResponse>
+    for SuspenseElement<Fut, P, T, X, Env>
+where
+    Fut: Future<Output: Blueprint<Env>>,
+    P: Blueprint<Env>,
+    X: Blueprint<Env>,
+{
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
+        if let Some(content) = self.content.as_mut()
+            && content.element.is_some()
+        {
+            return content.bubble(cx);
+        }
+
+        match (self.placeholder.as_mut(), self.timed_out.as_mut()) {
+            (Some(placeholder), _) => placeholder.bubble(cx),
+            (None, Some(timed_out)) => timed_out.bubble(cx),
+            (None, None) => EventResponse::default(),
+        }
+    }
+}
+
+impl<Fut, P, T, X, Env: Environment> Element<Env>
+    for SuspenseElement<Fut, P, T, X, Env>
+where
+    Fut: Future<Output: Blueprint<Env>>,
+    P: Blueprint<Env>,
+    T: Future,
+    X: Blueprint<Env>,
+{
+    type Effect<'fx>
+        = (
+        Option<<ReactOnce<Fut, Env> as Element<Env>>::Effect<'fx>>,
+        (
+            Option<<P::Element as Element<Env>>::Effect<'fx>>,
+            Option<<X::Element as Element<Env>>::Effect<'fx>>,
+        ),
+    )
+    where
+        Self: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        (
+            self.content.as_ref().map(|content| content.effect()),
+            (
+                self.placeholder.as_ref().map(|p| p.effect()),
+                self.timed_out.as_ref().map(|t| t.effect()),
+            ),
+        )
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        let mut this = self.project();
+        let mut changed = if *this.placeholder_shown {
+            Poll::Ready(None)
+        } else {
+            *this.placeholder_shown = true;
+            Poll::Ready(Some(()))
+        };
+
+        if let Some(mut content) = this.content.as_mut().as_pin_mut() {
+            let poll = content.as_mut().poll(cx, env);
+
+            if content.element.is_some() {
+                // The future resolved, so the placeholder has done its job.
+                if this.placeholder.is_some() {
+                    this.placeholder.set(None);
+                    this.timeout.set(None);
+                    return Poll::Ready(Some(()));
+                }
+                return poll;
+            }
+
+            let timed_out = this
+                .timeout
+                .as_mut()
+                .as_pin_mut()
+                .is_some_and(|timeout| timeout.poll(cx).is_ready());
+
+            if timed_out {
+                this.content.set(None);
+                this.placeholder.set(None);
+                this.timeout.set(None);
+                if let Some(view) = this.timeout_view.take() {
+                    this.timed_out.set(Some(view.make(env)));
+                }
+                return Poll::Ready(Some(()));
+            }
+
+            // Still waiting on the future.
+            changed = changed.combine(poll);
+        }
+
+        let placeholder = match this.placeholder.as_pin_mut() {
+            Some(placeholder) => placeholder.poll(cx, env),
+            None => Poll::Ready(None),
+        };
+        let timed_out = match this.timed_out.as_pin_mut() {
+            Some(timed_out) => timed_out.poll(cx, env),
+            None => Poll::Ready(None),
+        };
+        changed.combine(placeholder).combine(timed_out)
+    }
+}
+
+/// Like [`ReactOnce`], but for futures that might fail.
+///
+/// The error is given to whatever made this element through [`TryBlueprint::take_error`],
+/// like an [`ErrorBoundary`](crate::app::composition::elements::error_boundary::ErrorBoundary).
+#[pin_project]
+#[must_use = "TryReactOnce does nothing unless polled"]
+pub struct TryReactOnce<Fut, A, E, Env: Environment>
+where
+    Fut: Future<Output = Result<A, E>>,
+    A: Blueprint<Env>,
+{
+    #[pin]
+    future: Fut,
+    element: Option<A::Element>,
+    error: Option<E>,
+    /// Whether the future is done, so that it's not polled again.
+    resolved: bool,
+}
+
+impl<Fut, A, E, Env: Environment> TryBlueprint<Env>
+    for TryReactOnce<Fut, A, E, Env>
+where
+    Fut: Future<Output = Result<A, E>>,
+    A: Blueprint<Env>,
+{
+    type Element = Self;
+    type Error = E;
+
+    fn try_make(
+        self,
+        _: &Env::BlueprintResources<'_>,
+    ) -> Result<Self::Element, Self::Error> {
+        Ok(self)
+    }
+
+    fn take_error(element: Pin<&mut Self::Element>) -> Option<Self::Error> {
+        element.project().error.take()
+    }
+}
+
+impl<Fut, A, E, Env: Environment> Bubble<Event, EventResponse>
+    for TryReactOnce<Fut, A, E, Env>
+where
+    Fut: Future<Output = Result<A, E>>,
+    A: Blueprint<Env>,
+{
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
+        self.element
+            .as_mut()
+            .map(|e| e.bubble(cx))
+            .unwrap_or_default()
+    }
+}
+
+impl<Fut, A, E, Env: Environment> Element<Env> for TryReactOnce<Fut, A, E, Env>
+where
+    Fut: Future<Output = Result<A, E>>,
+    A: Blueprint<Env>,
+{
+    type Effect<'fx>
+        = Option<<A::Element as Element<Env>>::Effect<'fx>>
+    where
+        Self: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        self.element.as_ref().map(|e| e.effect())
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        let this = self.project();
+
+        if let Some(element) = this.element {
+            // SAFETY: we can pin element here because `self` is pinned.
+            return unsafe { Pin::new_unchecked(element) }.poll(cx, env);
+        }
+
+        if *this.resolved {
+            return Poll::Ready(None);
+        }
+
+        match this.future.poll(cx) {
+            Poll::Ready(Ok(blueprint)) => {
+                *this.resolved = true;
+                let element = this.element.insert(blueprint.make(env));
+
+                // Wake up the element.
+                // SAFETY: The element is never moved out of this struct,
+                // as if it was pinned in it.
+                let _ = unsafe { Pin::new_unchecked(element) }.poll(cx, env);
+
+                Poll::Ready(Some(()))
+            }
+            Poll::Ready(Err(error)) => {
+                *this.resolved = true;
+                *this.error = Some(error);
+                Poll::Ready(Some(()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Turns futures that might fail into [`TryBlueprint`]s.
+pub trait IntoTryBlueprint<Env: Environment> {
+    type Output: TryBlueprint<Env>;
+
+    fn into_try_blueprint(self) -> Self::Output;
+}
+
+impl<Fut, A, E, Env> IntoTryBlueprint<Env> for Fut
+where
+    Fut: Future<Output = Result<A, E>>,
+    A: Blueprint<Env>,
+    Env: Environment,
+{
+    type Output = TryReactOnce<Fut, A, E, Env>;
+
+    fn into_try_blueprint(self) -> Self::Output {
+        TryReactOnce {
+            future: self,
+            element: None,
+            error: None,
+            resolved: false,
+        }
+    }
+}
@@ -1,5 +1,9 @@
 pub mod future;
+pub mod resource;
 pub mod signal;
+pub mod signal_map;
+pub mod signal_vec;
+pub mod stream;
 
 /// An effect that some element of a structure might produce.
 ///
@@ -0,0 +1,175 @@
+//! # Resources
+//!
+//! [`Effect`]s happen all at once, so they can't wait for anything (say, an HTTP request).
+//! An [`AsyncEffect`] instead spawns a future on the app's [`AsyncExecutor`],
+//! and its [`Resource`] tells how that future is doing, so that the UI can show it.
+//!
+//! ```rust,ignore
+//! let user = Resource::new();
+//! let load_user = user.effect(move || fetch_user(id));
+//!
+//! (
+//!     Button(Label("Load"), load_user),
+//!     user.status()
+//!         .map(|status| match status {
+//!             Status::Idle => Label("Nothing yet"),
+//!             Status::Loading => Label("Loading..."),
+//!             Status::Success(user) => Label(user.name),
+//!             Status::Error(error) => Label(error.to_string()),
+//!         })
+//!         .into_blueprint(),
+//! )
+//! ```
+//!
+//! [`AsyncExecutor`]: crate::app::runner::futures::AsyncExecutor
+
+use crate::app::runner::futures::Spawner;
+use futures_signals::signal::{Mutable, Signal};
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use ui_composer_state::effect::Effect;
+
+/// How the latest run of a [`Resource`]'s future went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status<T, E> {
+    /// Nothing was run yet.
+    Idle,
+    Loading,
+    Success(T),
+    Error(E),
+}
+
+impl<T, E> Status<T, E> {
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Status::Loading)
+    }
+}
+
+/// The result of some future that's run by [`AsyncEffect`]s,
+/// see the [module docs](self).
+pub struct Resource<T, E> {
+    status: Mutable<Status<T, E>>,
+    /// How many times a future was run, so that a future can tell
+    /// whether a newer one replaced it.
+    runs: Arc<AtomicU64>,
+}
+
+impl<T, E> Resource<T, E> {
+    pub fn new() -> Self {
+        Self {
+            status: Mutable::new(Status::Idle),
+            runs: Arc::default(),
+        }
+    }
+
+    /// A signal of the resource's status.
+    pub fn status(&self) -> impl Signal<Item = Status<T, E>> + use<T, E>
+    where
+        T: Clone,
+        E: Clone,
+    {
+        self.status.signal_cloned()
+    }
+
+    /// A signal of whether a future is running.
+    pub fn is_loading(&self) -> impl Signal<Item = bool> + use<T, E> {
+        self.status.signal_ref(Status::is_loading)
+    }
+
+    /// The resource's status right now.
+    pub fn get(&self) -> Status<T, E>
+    where
+        T: Clone,
+        E: Clone,
+    {
+        self.status.get_cloned()
+    }
+
+    /// Goes back to being [idle](Status::Idle), ignoring the result of any running future.
+    pub fn reset(&self) {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        self.status.set(Status::Idle);
+    }
+
+    /// An effect that runs the future made by `f`,
+    /// on the [global spawner](Spawner::global).
+    pub fn effect<F, Fut>(&self, f: F) -> AsyncEffect<F, T, E>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        AsyncEffect {
+            make: f,
+            resource: self.clone(),
+            spawner: Spawner::global().clone(),
+        }
+    }
+}
+
+impl<T, E> Clone for Resource<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            status: self.status.clone(),
+            runs: self.runs.clone(),
+        }
+    }
+}
+
+impl<T, E> Default for Resource<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An effect that runs a future, writing its result into a [`Resource`],
+/// made with [`Resource::effect`].
+///
+/// Applying it again while the future runs replaces that future's result with the new one's.
+pub struct AsyncEffect<F, T, E> {
+    make: F,
+    resource: Resource<T, E>,
+    spawner: Spawner,
+}
+
+impl<F, T, E> AsyncEffect<F, T, E> {
+    /// Spawns futures on `spawner`, which should be the one of the app's executor.
+    pub fn with_spawner(self, spawner: Spawner) -> Self {
+        Self { spawner, ..self }
+    }
+}
+
+impl<F: Clone, T, E> Clone for AsyncEffect<F, T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            make: self.make.clone(),
+            resource: self.resource.clone(),
+            spawner: self.spawner.clone(),
+        }
+    }
+}
+
+impl<F, Fut, T, E> Effect for AsyncEffect<F, T, E>
+where
+    F: Fn() -> Fut + Clone + Send + Sync,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+    T: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    fn apply(&mut self) {
+        let run = self.resource.runs.fetch_add(1, Ordering::SeqCst) + 1;
+        self.resource.status.set(Status::Loading);
+
+        let future = (self.make)();
+        let resource = self.resource.clone();
+        self.spawner.spawn(async move {
+            let result = future.await;
+            if resource.runs.load(Ordering::SeqCst) == run {
+                resource.status.set(match result {
+                    Ok(value) => Status::Success(value),
+                    Err(error) => Status::Error(error),
+                });
+            }
+        });
+    }
+}
@@ -1,4 +1,5 @@
 use crate::app::composition::algebra::{Bubble, Semigroup as _};
+use crate::app::composition::elements::response::EventResponse;
 use crate::app::composition::elements::{Blueprint, Element, Environment};
 use futures_signals::signal::Signal;
 use pin_project::pin_project;
@@ -29,11 +30,11 @@ where
     }
 }
 
-impl<Sig, Env: Environment> Bubble<Event, bool> for React<Sig, Env>
+impl<Sig, Env: Environment> Bubble<Event, EventResponse> for React<Sig, Env>
 where
     Sig: Signal<Item: Blueprint<Env>>,
 {
-    fn bubble(&mut self, cx: &mut Event) -> bool {
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
         self.element
             .as_mut()
             .map(|e| e.bubble(cx))
@@ -70,12 +71,20 @@ where
         // SAFETY: Because the signal is pinned in this struct, its captures are stable.
         let signal_poll = match this.signal.poll_change(cx) {
             Poll::Ready(Some(blueprint)) => {
-                let mut element = blueprint.make(env);
+                if let Some(element) = this.element {
+                    // SAFETY: The element is never moved out of this struct,
+                    // as if it was pinned in it.
+                    blueprint
+                        .reconcile(unsafe { Pin::new_unchecked(element) }, env);
+                } else {
+                    *this.element = Some(blueprint.make(env));
+                }
 
                 // Wake up the element.
-                let _ =
-                    unsafe { Pin::new_unchecked(&mut element) }.poll(cx, env);
-                *this.element = Some(element);
+                if let Some(element) = this.element {
+                    let _ =
+                        unsafe { Pin::new_unchecked(element) }.poll(cx, env);
+                }
 
                 Poll::Ready(Some(()))
             }
@@ -0,0 +1,184 @@
+use crate::app::composition::algebra::implementations::bubble::bubble_each;
+use crate::app::composition::algebra::{Bubble, Empty, Semigroup as _};
+use crate::app::composition::effects::signal_vec::PinnedElement;
+use crate::app::composition::elements::response::EventResponse;
+use crate::app::composition::elements::{Blueprint, Element, Environment};
+use futures_signals::signal_map::{MapDiff, SignalMap};
+use pin_project::pin_project;
+use std::collections::BTreeMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use ui_composer_input::event::Event;
+
+/// A map of elements that follows a [`SignalMap`] of blueprints,
+/// keeping one element per key, in key order.
+///
+/// The [`MapDiff`]s emitted by the signal are applied to the existing elements,
+/// so entries which did not change keep their state,
+/// and updated ones are [reconciled](Blueprint::reconcile).
+#[pin_project]
+#[must_use = "ReactMap does nothing unless polled"]
+pub struct ReactMap<SigMap, Env: Environment>
+where
+    SigMap: SignalMap,
+    SigMap::Key: Ord,
+    SigMap::Value: Blueprint<Env>,
+{
+    #[pin]
+    signal_map: SigMap,
+    // Elements are boxed so that rebalancing the map does not move them in memory.
+    elements: BTreeMap<SigMap::Key, PinnedElement<SigMap::Value, Env>>,
+}
+
+impl<SigMap, Env: Environment> Blueprint<Env> for ReactMap<SigMap, Env>
+where
+    SigMap: SignalMap<Key: Ord, Value: Blueprint<Env>>,
+{
+    type Element = Self;
+
+    fn make(self, _: &Env::BlueprintResources<'_>) -> Self::Element {
+        self
+    }
+}
+
+impl<SigMap, Env: Environment> Bubble<Event, EventResponse>
+    for ReactMap<SigMap, Env>
+where
+    SigMap: SignalMap<Key: Ord, Value: Blueprint<Env>>,
+{
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
+        let elements = self.elements.values_mut().map(|element| {
+            // SAFETY: bubbling does not move the element.
+            unsafe { element.as_mut().get_unchecked_mut() }
+        });
+        bubble_each(elements, cx)
+    }
+}
+
+impl<SigMap, Env: Environment> Element<Env> for ReactMap<SigMap, Env>
+where
+    SigMap: SignalMap<Key: Ord, Value: Blueprint<Env>>,
+{
+    type Effect<'fx>
+        = Vec<
+        <<<SigMap as SignalMap>::Value as Blueprint<Env>>::Element as Element<
+            Env,
+        >>::Effect<'fx>,
+    >
+    where
+        Self: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        self.elements
+            .values()
+            .map(|element| element.effect())
+            .collect()
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        let mut this = self.project();
+
+        let mut signal_poll = Poll::Pending;
+        loop {
+            match this.signal_map.as_mut().poll_map_change(cx) {
+                Poll::Ready(Some(diff)) => {
+                    apply_diff::<SigMap::Key, SigMap::Value, Env>(
+                        diff,
+                        this.elements,
+                        env,
+                    );
+                    signal_poll = Poll::Ready(Some(()));
+                }
+                Poll::Ready(None) => {
+                    if signal_poll.is_pending() {
+                        signal_poll = Poll::Ready(None);
+                    }
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        let elements_poll: Poll<Option<()>> = this
+            .elements
+            .values_mut()
+            .fold(Empty::empty(), |acc, element| {
+                acc.combine(element.as_mut().poll(cx, env))
+            });
+
+        signal_poll.combine(elements_poll)
+    }
+}
+
+fn apply_diff<Key, Value, Env>(
+    diff: MapDiff<Key, Value>,
+    elements: &mut BTreeMap<Key, Pin<Box<Value::Element>>>,
+    env: &Env::BlueprintResources<'_>,
+) where
+    Key: Ord,
+    Value: Blueprint<Env>,
+    Env: Environment,
+{
+    match diff {
+        MapDiff::Replace { entries } => {
+            let mut old_elements = core::mem::take(elements);
+
+            for (key, value) in entries {
+                let element = match old_elements.remove(&key) {
+                    Some(mut element) => {
+                        value.reconcile(element.as_mut(), env);
+                        element
+                    }
+                    None => Box::pin(value.make(env)),
+                };
+                elements.insert(key, element);
+            }
+        }
+        MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+            match elements.get_mut(&key) {
+                Some(element) => value.reconcile(element.as_mut(), env),
+                None => {
+                    elements.insert(key, Box::pin(value.make(env)));
+                }
+            }
+        }
+        MapDiff::Remove { key } => {
+            elements.remove(&key);
+        }
+        MapDiff::Clear {} => {
+            elements.clear();
+        }
+    }
+}
+
+/*
+    Like the other `IntoBlueprint`s, this is necessary
+    while we don't have `min_specialization`.
+*/
+
+pub trait IntoBlueprint<Env: Environment> {
+    type Output: Blueprint<Env>;
+
+    fn into_blueprint(self) -> Self::Output;
+}
+
+impl<SigMap, Env> IntoBlueprint<Env> for SigMap
+where
+    SigMap: SignalMap,
+    Env: Environment,
+    SigMap::Key: Ord,
+    SigMap::Value: Blueprint<Env>,
+{
+    type Output = ReactMap<SigMap, Env>;
+
+    fn into_blueprint(self) -> Self::Output {
+        ReactMap {
+            signal_map: self,
+            elements: BTreeMap::new(),
+        }
+    }
+}
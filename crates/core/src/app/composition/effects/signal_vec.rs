@@ -0,0 +1,294 @@
+use crate::app::composition::algebra::implementations::bubble::bubble_each;
+use crate::app::composition::algebra::{Bubble, Empty, Semigroup as _};
+use crate::app::composition::elements::response::EventResponse;
+use crate::app::composition::elements::{Blueprint, Element, Environment};
+use futures_signals::signal_vec::{SignalVec, VecDiff};
+use pin_project::pin_project;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use ui_composer_input::event::Event;
+
+/// The element of a blueprint, pinned where it was made.
+pub(crate) type PinnedElement<B, Env> =
+    Pin<Box<<B as Blueprint<Env>>::Element>>;
+
+/// A list of elements that follows a [`SignalVec`] of blueprints.
+///
+/// Instead of rebuilding the whole list whenever it changes,
+/// the [`VecDiff`]s emitted by the signal are applied to the existing elements,
+/// so untouched elements keep their state, and updated ones are
+/// [reconciled](Blueprint::reconcile).
+#[pin_project]
+#[must_use = "ReactVec does nothing unless polled"]
+pub struct ReactVec<SigVec, Env: Environment, Key = Unkeyed>
+where
+    SigVec: SignalVec,
+    SigVec::Item: Blueprint<Env>,
+    Key: ItemKey<SigVec::Item>,
+{
+    #[pin]
+    signal_vec: SigVec,
+    key: Key,
+    keys: Vec<Key::Key>,
+    // Elements are boxed so that moving them around the list does not move them in memory.
+    elements: Vec<PinnedElement<SigVec::Item, Env>>,
+}
+
+impl<SigVec, Env: Environment> ReactVec<SigVec, Env, Unkeyed>
+where
+    SigVec: SignalVec<Item: Blueprint<Env>>,
+{
+    /// Identifies each item of the list by a key.
+    ///
+    /// When the whole list is replaced, or an item is updated, elements
+    /// whose key did not change are reconciled instead of being made anew.
+    pub fn with_key<F, K>(self, key: F) -> ReactVec<SigVec, Env, Keyed<F, K>>
+    where
+        F: FnMut(&SigVec::Item) -> K,
+        K: PartialEq,
+    {
+        ReactVec {
+            signal_vec: self.signal_vec,
+            key: Keyed(key, PhantomData),
+            keys: Vec::new(),
+            elements: Vec::new(),
+        }
+    }
+}
+
+/// Describes which items of a [`ReactVec`] refer to the same thing.
+pub trait ItemKey<Item> {
+    type Key;
+
+    fn key(&mut self, item: &Item) -> Self::Key;
+
+    fn same(a: &Self::Key, b: &Self::Key) -> bool;
+}
+
+/// Items are matched by their position in the list.
+pub struct Unkeyed;
+
+impl<Item> ItemKey<Item> for Unkeyed {
+    type Key = ();
+
+    fn key(&mut self, _: &Item) -> Self::Key {}
+
+    fn same(_: &Self::Key, _: &Self::Key) -> bool {
+        true
+    }
+}
+
+/// Items with equal keys are considered the same.
+pub struct Keyed<F, K>(F, PhantomData<fn() -> K>);
+
+impl<Item, F, K> ItemKey<Item> for Keyed<F, K>
+where
+    F: FnMut(&Item) -> K,
+    K: PartialEq,
+{
+    type Key = K;
+
+    fn key(&mut self, item: &Item) -> Self::Key {
+        (self.0)(item)
+    }
+
+    fn same(a: &Self::Key, b: &Self::Key) -> bool {
+        a == b
+    }
+}
+
+impl<SigVec, Env: Environment, Key> Blueprint<Env>
+    for ReactVec<SigVec, Env, Key>
+where
+    SigVec: SignalVec<Item: Blueprint<Env>>,
+    Key: ItemKey<SigVec::Item>,
+{
+    type Element = Self;
+
+    fn make(self, _: &Env::BlueprintResources<'_>) -> Self::Element {
+        self
+    }
+}
+
+impl<SigVec, Env: Environment, Key> Bubble<Event, EventResponse>
+    for ReactVec<SigVec, Env, Key>
+where
+    SigVec: SignalVec<Item: Blueprint<Env>>,
+    Key: ItemKey<SigVec::Item>,
+{
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
+        let elements = self.elements.iter_mut().map(|element| {
+            // SAFETY: bubbling does not move the element.
+            unsafe { element.as_mut().get_unchecked_mut() }
+        });
+        bubble_each(elements, cx)
+    }
+}
+
+impl<SigVec, Env: Environment, Key> Element<Env> for ReactVec<SigVec, Env, Key>
+where
+    SigVec: SignalVec<Item: Blueprint<Env>>,
+    Key: ItemKey<SigVec::Item>,
+{
+    type Effect<'fx>
+        = Vec<
+        <<<SigVec as SignalVec>::Item as Blueprint<Env>>::Element as Element<
+            Env,
+        >>::Effect<'fx>,
+    >
+    where
+        Self: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        self.elements
+            .iter()
+            .map(|element| element.effect())
+            .collect()
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        let mut this = self.project();
+
+        let mut signal_poll = Poll::Pending;
+        loop {
+            match this.signal_vec.as_mut().poll_vec_change(cx) {
+                Poll::Ready(Some(diff)) => {
+                    apply_diff::<SigVec::Item, Env, Key>(
+                        diff,
+                        this.key,
+                        this.keys,
+                        this.elements,
+                        env,
+                    );
+                    signal_poll = Poll::Ready(Some(()));
+                }
+                Poll::Ready(None) => {
+                    if signal_poll.is_pending() {
+                        signal_poll = Poll::Ready(None);
+                    }
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        let elements_poll: Poll<Option<()>> = this
+            .elements
+            .iter_mut()
+            .fold(Empty::empty(), |acc, element| {
+                acc.combine(element.as_mut().poll(cx, env))
+            });
+
+        signal_poll.combine(elements_poll)
+    }
+}
+
+fn apply_diff<Item, Env, Key>(
+    diff: VecDiff<Item>,
+    key: &mut Key,
+    keys: &mut Vec<Key::Key>,
+    elements: &mut Vec<Pin<Box<Item::Element>>>,
+    env: &Env::BlueprintResources<'_>,
+) where
+    Item: Blueprint<Env>,
+    Env: Environment,
+    Key: ItemKey<Item>,
+{
+    match diff {
+        VecDiff::Replace { values } => {
+            let mut old_keys = core::mem::take(keys);
+            let mut old_elements = core::mem::take(elements);
+
+            for value in values {
+                let new_key = key.key(&value);
+                let reusable =
+                    old_keys.iter().position(|k| Key::same(k, &new_key));
+
+                let element = match reusable {
+                    Some(index) => {
+                        old_keys.remove(index);
+                        let mut element = old_elements.remove(index);
+                        value.reconcile(element.as_mut(), env);
+                        element
+                    }
+                    None => Box::pin(value.make(env)),
+                };
+
+                keys.push(new_key);
+                elements.push(element);
+            }
+        }
+        VecDiff::InsertAt { index, value } => {
+            keys.insert(index, key.key(&value));
+            elements.insert(index, Box::pin(value.make(env)));
+        }
+        VecDiff::UpdateAt { index, value } => {
+            let new_key = key.key(&value);
+            if Key::same(&keys[index], &new_key) {
+                value.reconcile(elements[index].as_mut(), env);
+            } else {
+                elements[index] = Box::pin(value.make(env));
+            }
+            keys[index] = new_key;
+        }
+        VecDiff::RemoveAt { index } => {
+            keys.remove(index);
+            elements.remove(index);
+        }
+        VecDiff::Move {
+            old_index,
+            new_index,
+        } => {
+            let moved_key = keys.remove(old_index);
+            let moved_element = elements.remove(old_index);
+            keys.insert(new_index, moved_key);
+            elements.insert(new_index, moved_element);
+        }
+        VecDiff::Push { value } => {
+            keys.push(key.key(&value));
+            elements.push(Box::pin(value.make(env)));
+        }
+        VecDiff::Pop {} => {
+            keys.pop();
+            elements.pop();
+        }
+        VecDiff::Clear {} => {
+            keys.clear();
+            elements.clear();
+        }
+    }
+}
+
+/*
+    Like the other `IntoBlueprint`s, this is necessary
+    while we don't have `min_specialization`.
+*/
+
+pub trait IntoBlueprint<Env: Environment> {
+    type Output: Blueprint<Env>;
+
+    fn into_blueprint(self) -> Self::Output;
+}
+
+impl<SigVec, Env> IntoBlueprint<Env> for SigVec
+where
+    SigVec: SignalVec,
+    Env: Environment,
+    SigVec::Item: Blueprint<Env>,
+{
+    type Output = ReactVec<SigVec, Env>;
+
+    fn into_blueprint(self) -> Self::Output {
+        ReactVec {
+            signal_vec: self,
+            key: Unkeyed,
+            keys: Vec::new(),
+            elements: Vec::new(),
+        }
+    }
+}
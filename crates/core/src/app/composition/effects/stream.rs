@@ -0,0 +1,198 @@
+use crate::app::composition::algebra::{Bubble, Semigroup as _};
+use crate::app::composition::elements::response::EventResponse;
+use crate::app::composition::elements::{Blueprint, Element, Environment};
+use futures::stream::{Fuse, Stream, StreamExt as _};
+use pin_project::pin_project;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use ui_composer_input::event::Event;
+
+/// An element that is remade (or reconciled) with every item of a [`Stream`].
+///
+/// Unlike a [`Signal`](futures_signals::signal::Signal), a stream doesn't skip items:
+/// each poll takes a single item, so every one of them is shown in turn,
+/// and a stream that's always ready can't keep the app from presenting.
+/// To show all of them at once (say, the lines of a log),
+/// [`accumulate`](Accumulate::accumulate) them before turning the stream into a blueprint.
+#[pin_project]
+#[must_use = "ReactStream does nothing unless polled"]
+pub struct ReactStream<St, Env: Environment>
+where
+    St: Stream,
+    St::Item: Blueprint<Env>,
+{
+    #[pin]
+    stream: Fuse<St>,
+    element: Option<<St::Item as Blueprint<Env>>::Element>,
+}
+
+impl<St, Env: Environment> Blueprint<Env> for ReactStream<St, Env>
+where
+    St: Stream<Item: Blueprint<Env>>,
+{
+    type Element = Self;
+
+    fn make(self, _: &Env::BlueprintResources<'_>) -> Self::Element {
+        self
+    }
+}
+
+impl<St, Env: Environment> Bubble<Event, EventResponse> for ReactStream<St, Env>
+where
+    St: Stream<Item: Blueprint<Env>>,
+{
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
+        self.element
+            .as_mut()
+            .map(|e| e.bubble(cx))
+            .unwrap_or_default()
+    }
+}
+
+impl<St, Env: Environment> Element<Env> for ReactStream<St, Env>
+where
+    St: Stream<Item: Blueprint<Env>>,
+{
+    type Effect<'fx>
+        =
+        Option<
+            <<<St as Stream>::Item as Blueprint<Env>>::Element as Element<
+                Env,
+            >>::Effect<'fx>,
+        >
+    where
+        St: 'fx,
+        Env: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        self.element.as_ref().map(|e| e.effect())
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        let mut this = self.project();
+
+        let stream_poll = match this.stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(blueprint)) => {
+                if let Some(element) = this.element {
+                    // SAFETY: The element is never moved out of this struct,
+                    // as if it was pinned in it.
+                    blueprint
+                        .reconcile(unsafe { Pin::new_unchecked(element) }, env);
+                } else {
+                    *this.element = Some(blueprint.make(env));
+                }
+
+                // Wake up the element.
+                if let Some(element) = this.element {
+                    let _ =
+                        unsafe { Pin::new_unchecked(element) }.poll(cx, env);
+                }
+
+                Poll::Ready(Some(()))
+            }
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(None) => Poll::Ready(None),
+        };
+
+        let element_poll = this
+            .element
+            .as_mut()
+            .map(|element| unsafe { Pin::new_unchecked(element) }.poll(cx, env))
+            .unwrap_or(Poll::Pending);
+
+        stream_poll.combine(element_poll)
+    }
+}
+
+/*
+    This is necessary while we don't have `min_specialization`.
+
+    We can't implement `Blueprint` for all streams without problems,
+    so we need to a type this crate owns.
+*/
+
+pub trait IntoBlueprint<Env: Environment> {
+    type Output: Blueprint<Env>;
+
+    fn into_blueprint(self) -> Self::Output;
+}
+
+impl<St, Env> IntoBlueprint<Env> for St
+where
+    St: Stream,
+    Env: Environment,
+    St::Item: Blueprint<Env>,
+{
+    type Output = ReactStream<St, Env>;
+
+    fn into_blueprint(self) -> Self::Output {
+        ReactStream {
+            stream: self.fuse(),
+            element: None,
+        }
+    }
+}
+
+/// Accumulates the items of a stream, so that none of them is lost.
+pub trait Accumulate: Stream + Sized {
+    /// Calls `f` with every item of this stream and `acc`,
+    /// making a stream of whatever `f` returns.
+    ///
+    /// ```rust,ignore
+    /// progress_lines
+    ///     .accumulate(Vec::new(), |log, line| {
+    ///         log.push(line);
+    ///         log_view(log)
+    ///     })
+    ///     .into_blueprint()
+    /// ```
+    fn accumulate<Acc, F, B>(self, acc: Acc, f: F) -> Accumulated<Self, Acc, F>
+    where
+        F: FnMut(&mut Acc, Self::Item) -> B,
+    {
+        Accumulated {
+            stream: self,
+            acc,
+            f,
+        }
+    }
+}
+
+impl<St: Stream> Accumulate for St {}
+
+/// The stream returned by [`Accumulate::accumulate`].
+#[pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct Accumulated<St, Acc, F> {
+    #[pin]
+    stream: St,
+    acc: Acc,
+    f: F,
+}
+
+impl<St, Acc, F, B> Stream for Accumulated<St, Acc, F>
+where
+    St: Stream,
+    F: FnMut(&mut Acc, St::Item) -> B,
+{
+    type Item = B;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        this.stream
+            .poll_next(cx)
+            .map(|item| item.map(|item| (this.f)(this.acc, item)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
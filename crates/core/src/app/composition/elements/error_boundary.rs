@@ -0,0 +1,295 @@
+//! # Error Boundaries
+//!
+//! An [`ErrorBoundary`] makes its content with [`TryBlueprint::try_make`] and,
+//! if that fails (or, optionally, panics), shows some fallback UI instead of crashing the app.
+//!
+//! What was caught is kept in a [`Mutable`], so it can be logged or shown on the fallback UI.
+//!
+//! ```rust,ignore
+//! let error_state = Mutable::new(None);
+//!
+//! let boundary = ErrorBoundary::new(
+//!     fetch_text().map(|text| text.map(|text| Text { rect, text, color })).into_try_blueprint(),
+//!     Text { rect, text: "Couldn't fetch the text :-(".into(), color },
+//! )
+//! .with_panics_caught()
+//! .with_error_state(error_state.clone());
+//! ```
+//!
+//! When the boundary is reconciled while showing its fallback, the new content is tried again.
+
+use crate::app::composition::algebra::{Bubble, Empty, Semigroup as _};
+use crate::app::composition::elements::response::EventResponse;
+use crate::app::composition::elements::{
+    Blueprint, Element, Environment, TryBlueprint,
+};
+use futures_signals::signal::{Mutable, MutableSignalCloned};
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use ui_composer_input::event::Event;
+
+/// Something an [`ErrorBoundary`] caught from its content.
+#[derive(Debug)]
+pub enum Caught<E> {
+    /// The content failed with an error.
+    Error(E),
+    /// The content panicked, with this message.
+    Panic(String),
+}
+
+impl<E: Display> Display for Caught<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Caught::Error(error) => error.fmt(f),
+            Caught::Panic(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+/// The last thing an [`ErrorBoundary`] caught,
+/// or `None` if its content is being shown.
+pub type ErrorState<E> = Mutable<Option<Arc<Caught<E>>>>;
+
+/// A blueprint that shows `fallback` if its `content` fails,
+/// see the [module docs](self).
+pub struct ErrorBoundary<C, F, E> {
+    content: C,
+    fallback: F,
+    error_state: ErrorState<E>,
+    catch_panics: bool,
+}
+
+impl<C, F, E> ErrorBoundary<C, F, E> {
+    pub fn new(content: C, fallback: F) -> Self {
+        Self {
+            content,
+            fallback,
+            error_state: Mutable::new(None),
+            catch_panics: false,
+        }
+    }
+
+    /// Also catches panics from making, reconciling and polling the content,
+    /// as if they were errors.
+    pub fn with_panics_caught(self) -> Self {
+        Self {
+            catch_panics: true,
+            ..self
+        }
+    }
+
+    /// Shares what this boundary catches with `error_state`.
+    pub fn with_error_state(self, error_state: ErrorState<E>) -> Self {
+        Self {
+            error_state,
+            ..self
+        }
+    }
+
+    /// A signal of the last thing this boundary caught.
+    pub fn error_signal(&self) -> MutableSignalCloned<Option<Arc<Caught<E>>>> {
+        self.error_state.signal_cloned()
+    }
+}
+
+impl<C, F, E, Env> Blueprint<Env> for ErrorBoundary<C, F, E>
+where
+    Env: Environment,
+    C: TryBlueprint<Env, Error = E>,
+    F: Blueprint<Env>,
+{
+    type Element = ErrorBoundaryElement<C, F, E, Env>;
+
+    fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
+        let mut element = ErrorBoundaryElement {
+            content: None,
+            fallback: None,
+            fallback_blueprint: Some(self.fallback),
+            error_state: self.error_state,
+            catch_panics: self.catch_panics,
+        };
+        element.show(self.content, env);
+        element
+    }
+
+    fn reconcile(
+        self,
+        element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) {
+        // SAFETY: The content and the fallback are pinned in their own boxes.
+        let element = unsafe { element.get_unchecked_mut() };
+        element.fallback_blueprint = Some(self.fallback);
+        element.error_state = self.error_state;
+        element.catch_panics = self.catch_panics;
+
+        let Some(content) = element.content.as_mut() else {
+            // Maybe the content works now...
+            return element.show(self.content, env);
+        };
+
+        let result = catching(element.catch_panics, || {
+            self.content.try_reconcile(content.as_mut(), env)
+        });
+        if let Err(caught) = result {
+            element.fail(caught, env);
+        }
+    }
+}
+
+/// The element made by an [`ErrorBoundary`].
+pub struct ErrorBoundaryElement<C, F, E, Env>
+where
+    Env: Environment,
+    C: TryBlueprint<Env, Error = E>,
+    F: Blueprint<Env>,
+{
+    content: Option<Pin<Box<C::Element>>>,
+    fallback: Option<Pin<Box<F::Element>>>,
+    /// The fallback to make (or reconcile with) the next time the content fails.
+    fallback_blueprint: Option<F>,
+    error_state: ErrorState<E>,
+    catch_panics: bool,
+}
+
+impl<C, F, E, Env> ErrorBoundaryElement<C, F, E, Env>
+where
+    Env: Environment,
+    C: TryBlueprint<Env, Error = E>,
+    F: Blueprint<Env>,
+{
+    /// Tries to make `content`, and shows it if that works.
+    fn show(&mut self, content: C, env: &Env::BlueprintResources<'_>) {
+        match catching(self.catch_panics, || content.try_make(env)) {
+            Ok(content) => {
+                self.content = Some(Box::pin(content));
+                self.fallback = None;
+                if self.error_state.lock_ref().is_some() {
+                    self.error_state.set(None);
+                }
+            }
+            Err(caught) => self.fail(caught, env),
+        }
+    }
+
+    /// Drops the content, showing the fallback instead.
+    fn fail(&mut self, caught: Caught<E>, env: &Env::BlueprintResources<'_>) {
+        self.content = None;
+        self.error_state.set(Some(Arc::new(caught)));
+
+        match (self.fallback_blueprint.take(), self.fallback.as_mut()) {
+            (Some(blueprint), Some(fallback)) => {
+                blueprint.reconcile(fallback.as_mut(), env);
+            }
+            (Some(blueprint), None) => {
+                self.fallback = Some(Box::pin(blueprint.make(env)));
+            }
+            (None, _) => { /* The fallback is already up to date. */ }
+        }
+    }
+}
+
+impl<C, F, E, Env> Bubble<Event, EventResponse>
+    for ErrorBoundaryElement<C, F, E, Env>
+where
+    Env: Environment,
+    C: TryBlueprint<Env, Error = E>,
+    F: Blueprint<Env>,
+{
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
+        // SAFETY: Bubbling does not move the element.
+        match (self.content.as_mut(), self.fallback.as_mut()) {
+            (Some(content), _) => {
+                unsafe { content.as_mut().get_unchecked_mut() }.bubble(cx)
+            }
+            (None, Some(fallback)) => {
+                unsafe { fallback.as_mut().get_unchecked_mut() }.bubble(cx)
+            }
+            (None, None) => Empty::empty(),
+        }
+    }
+}
+
+impl<C, F, E, Env> Element<Env> for ErrorBoundaryElement<C, F, E, Env>
+where
+    Env: Environment,
+    C: TryBlueprint<Env, Error = E>,
+    F: Blueprint<Env>,
+{
+    type Effect<'fx>
+        = (
+        Option<<C::Element as Element<Env>>::Effect<'fx>>,
+        Option<<F::Element as Element<Env>>::Effect<'fx>>,
+    )
+    where
+        Self: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        (
+            self.content.as_ref().map(|content| content.effect()),
+            self.fallback.as_ref().map(|fallback| fallback.effect()),
+        )
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        // SAFETY: The content and the fallback are boxed,
+        // so they stay pinned wherever the element is moved to.
+        let this = unsafe { self.get_unchecked_mut() };
+
+        let mut changed = Empty::empty();
+        if let Some(content) = this.content.as_mut() {
+            let result = catching(this.catch_panics, || {
+                let poll = content.as_mut().poll(cx, env);
+                match C::take_error(content.as_mut()) {
+                    Some(error) => Err(error),
+                    None => Ok(poll),
+                }
+            });
+            match result {
+                Ok(poll) => return poll,
+                Err(caught) => {
+                    this.fail(caught, env);
+                    changed = Poll::Ready(Some(()));
+                }
+            }
+        }
+
+        match this.fallback.as_mut() {
+            Some(fallback) => changed.combine(fallback.as_mut().poll(cx, env)),
+            None => changed,
+        }
+    }
+}
+
+/// Runs `f`, turning its error (or its panic, if `catch_panics`) into a [`Caught`].
+fn catching<T, E>(
+    catch_panics: bool,
+    f: impl FnOnce() -> Result<T, E>,
+) -> Result<T, Caught<E>> {
+    if !catch_panics {
+        return f().map_err(Caught::Error);
+    }
+
+    match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result.map_err(Caught::Error),
+        Err(payload) => Err(Caught::Panic(panic_message(payload))),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Box<dyn Any>".to_string(),
+        },
+    }
+}
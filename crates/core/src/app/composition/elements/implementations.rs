@@ -1,4 +1,4 @@
-use super::{Blueprint, Element};
+use super::{Blueprint, Element, TryBlueprint};
 use crate::app::composition::algebra::Semigroup;
 use crate::app::composition::elements::Environment;
 use crate::prelude::Empty;
@@ -29,6 +29,15 @@ where
     fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
         Box::new(A::make(*self, env))
     }
+
+    fn reconcile(
+        self,
+        element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) {
+        let element = unsafe { element.map_unchecked_mut(|e| &mut **e) };
+        A::reconcile(*self, element, env)
+    }
 }
 
 impl<A, Env: Environment> Element<Env> for Box<A>
@@ -67,6 +76,16 @@ where
     fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
         (self.0.make(env), self.1.make(env))
     }
+
+    fn reconcile(
+        self,
+        element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) {
+        let (a, b) = unsafe { element.get_unchecked_mut() };
+        self.0.reconcile(unsafe { Pin::new_unchecked(a) }, env);
+        self.1.reconcile(unsafe { Pin::new_unchecked(b) }, env);
+    }
 }
 
 impl<A, B, Env: Environment> Element<Env> for (A, B)
@@ -115,6 +134,24 @@ where
     fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
         self.into_iter().map(|it| it.make(env)).collect()
     }
+
+    fn reconcile(
+        self,
+        mut element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) {
+        // Growing the vec could move pinned elements around in memory,
+        // so a list of a different length is made anew.
+        if self.len() != element.len() {
+            element.set(self.make(env));
+            return;
+        }
+
+        let items = unsafe { element.get_unchecked_mut() };
+        for (blueprint, item) in self.into_iter().zip(items.iter_mut()) {
+            blueprint.reconcile(unsafe { Pin::new_unchecked(item) }, env);
+        }
+    }
 }
 
 impl<A, Env: Environment> Element<Env> for Vec<A>
@@ -154,6 +191,19 @@ where
     fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
         self.map(|x| x.make(env))
     }
+
+    fn reconcile(
+        self,
+        mut element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) {
+        match self {
+            Some(blueprint) if element.is_some() => {
+                blueprint.reconcile(element.as_pin_mut().unwrap(), env)
+            }
+            blueprint => element.set(blueprint.make(env)),
+        }
+    }
 }
 
 impl<A, Env: Environment> Element<Env> for Option<A>
@@ -169,3 +219,28 @@ where
         self.as_ref().map(|x| x.effect())
     }
 }
+
+/* Results */
+
+impl<A, E, Env: Environment> TryBlueprint<Env> for Result<A, E>
+where
+    A: Blueprint<Env>,
+{
+    type Element = A::Element;
+    type Error = E;
+
+    fn try_make(
+        self,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Result<Self::Element, Self::Error> {
+        self.map(|x| x.make(env))
+    }
+
+    fn try_reconcile(
+        self,
+        element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Result<(), Self::Error> {
+        self.map(|x| x.reconcile(element, env))
+    }
+}
@@ -0,0 +1,113 @@
+use crate::app::composition::algebra::implementations::bubble::bubble_each;
+use crate::app::composition::algebra::{Bubble, Empty, Semigroup as _};
+use crate::app::composition::elements::response::EventResponse;
+use crate::app::composition::elements::{Blueprint, Element, Environment};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use ui_composer_input::event::Event;
+
+/// A list of blueprints, each identified by a key.
+///
+/// Unlike a [`Vec`] of blueprints, whose elements are all made anew when its length changes,
+/// reconciling a keyed list matches elements by key — so items that were kept
+/// keep their state, even if other items were added, removed or moved around.
+pub struct KeyedVec<K, A>(pub Vec<(K, A)>);
+
+impl<K, A> FromIterator<(K, A)> for KeyedVec<K, A> {
+    fn from_iter<I: IntoIterator<Item = (K, A)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// The element made by a [`KeyedVec`].
+pub struct KeyedVecElement<K, E> {
+    // Elements are boxed so that moving them around the list does not move them in memory.
+    entries: Vec<(K, Pin<Box<E>>)>,
+}
+
+impl<K, A, Env: Environment> Blueprint<Env> for KeyedVec<K, A>
+where
+    K: PartialEq,
+    A: Blueprint<Env>,
+{
+    type Element = KeyedVecElement<K, A::Element>;
+
+    fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
+        KeyedVecElement {
+            entries: self
+                .0
+                .into_iter()
+                .map(|(key, blueprint)| (key, Box::pin(blueprint.make(env))))
+                .collect(),
+        }
+    }
+
+    fn reconcile(
+        self,
+        element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) {
+        // SAFETY: Only the boxes pointing to the entries are moved around.
+        let element = unsafe { element.get_unchecked_mut() };
+        let mut old_entries = core::mem::take(&mut element.entries);
+
+        for (key, blueprint) in self.0 {
+            let reusable = old_entries.iter().position(|(old, _)| *old == key);
+
+            let entry = match reusable {
+                Some(index) => {
+                    let (_, mut old_element) = old_entries.remove(index);
+                    blueprint.reconcile(old_element.as_mut(), env);
+                    (key, old_element)
+                }
+                None => (key, Box::pin(blueprint.make(env))),
+            };
+
+            element.entries.push(entry);
+        }
+    }
+}
+
+impl<K, E> Bubble<Event, EventResponse> for KeyedVecElement<K, E>
+where
+    E: Bubble<Event, EventResponse>,
+{
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
+        let elements = self.entries.iter_mut().map(|(_, element)| {
+            // SAFETY: bubbling does not move the element.
+            unsafe { element.as_mut().get_unchecked_mut() }
+        });
+        bubble_each(elements, cx)
+    }
+}
+
+impl<K, E, Env: Environment> Element<Env> for KeyedVecElement<K, E>
+where
+    E: Element<Env>,
+{
+    type Effect<'fx>
+        = Vec<E::Effect<'fx>>
+    where
+        Self: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        self.entries
+            .iter()
+            .map(|(_, element)| element.effect())
+            .collect()
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        // SAFETY: the entries are not moved, only the boxes pointing to them.
+        let this = unsafe { self.get_unchecked_mut() };
+        this.entries
+            .iter_mut()
+            .fold(Empty::empty(), |acc, (_, element)| {
+                acc.combine(element.as_mut().poll(cx, env))
+            })
+    }
+}
@@ -10,11 +10,15 @@
 
 use crate::app::composition::algebra::Bubble;
 use crate::app::composition::visit::DriveThru;
+use response::EventResponse;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use ui_composer_input::event::Event;
 
+pub mod error_boundary;
 pub mod implementations;
+pub mod keyed;
+pub mod response;
 
 pub struct DummyEnvironment();
 
@@ -24,9 +28,76 @@ where
 {
     type Element: Element<Env>;
     fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element;
+
+    /// Patches an element previously made from a blueprint of this type
+    /// so that it matches this blueprint.
+    ///
+    /// By default, the element is simply made anew, but blueprints can override this
+    /// to update the element in place and keep whatever state it has accumulated.
+    ///
+    /// Reactive blueprints like [`React`] use this whenever their signal yields
+    /// a new blueprint while an element already exists.
+    ///
+    /// The element is pinned, since it might have been polled already:
+    /// it can be patched or [replaced](Pin::set), but not moved out of.
+    ///
+    /// [`React`]: crate::app::composition::effects::signal::React
+    fn reconcile(
+        self,
+        mut element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) where
+        Self: Sized,
+    {
+        element.set(self.make(env));
+    }
+}
+
+/// A [`Blueprint`] whose element might fail to be made.
+///
+/// Where a [`Blueprint`] would have to panic (or `expect`) when something goes wrong,
+/// a `TryBlueprint` returns an error instead, which an [`ErrorBoundary`] can catch
+/// and show some fallback UI in place of the element.
+///
+/// `Result<impl Blueprint, E>` is the simplest `TryBlueprint`.
+///
+/// [`ErrorBoundary`]: error_boundary::ErrorBoundary
+pub trait TryBlueprint<Env>
+where
+    Env: Environment,
+{
+    type Element: Element<Env>;
+    type Error;
+
+    fn try_make(
+        self,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Result<Self::Element, Self::Error>;
+
+    /// Like [`Blueprint::reconcile`], but might fail.
+    fn try_reconcile(
+        self,
+        mut element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        element.set(self.try_make(env)?);
+        Ok(())
+    }
+
+    /// Takes an error the element ran into after being made, while being polled.
+    ///
+    /// Most elements can only fail when they're made, so this returns nothing by default.
+    fn take_error(
+        #[expect(unused)] element: Pin<&mut Self::Element>,
+    ) -> Option<Self::Error> {
+        None
+    }
 }
 
-pub trait Element<Env: Environment>: Bubble<Event, bool> {
+pub trait Element<Env: Environment>: Bubble<Event, EventResponse> {
     type Effect<'fx>: DriveThru<Env::EffectVisitor<'fx>>
     where
         Self: 'fx;
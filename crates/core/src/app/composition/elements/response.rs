@@ -0,0 +1,163 @@
+//! # Event Responses
+//!
+//! When an [`Event`] is bubbled down an app, every element answers with an [`EventResponse`].
+//!
+//! Responses of sibling elements are [combined](Semigroup) on the way up,
+//! so the runner gets a single answer describing everything the app asked for:
+//! whether the event was handled, whether it should be redrawn, which cursor icon to show...
+//!
+//! [`Event`]: ui_composer_input::event::Event
+
+use crate::app::composition::algebra::{Empty, Propagate, Semigroup};
+use ui_composer_input::event::DeviceId;
+
+/// What an element did with an event and what it wants its runner to do.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[must_use = "runners should act on an element's response to an event"]
+pub struct EventResponse {
+    /// Whether some element did something in response to the event.
+    pub handled: bool,
+    /// Whether elements after the one that responded should not see the event.
+    pub stop_propagation: bool,
+    /// Asks for a pointer device to be captured (or released) by some element,
+    /// which a [`PointerScopeBlueprint`](crate::app::composition::pointer::PointerScopeBlueprint)
+    /// does by routing that pointer's events to it.
+    pub pointer_capture: Option<PointerCapture>,
+    /// Asks for keyboard focus to be given to some item.
+    pub request_focus: Option<FocusId>,
+    /// Asks the runner to redraw, even if nothing reported a change.
+    pub request_redraw: bool,
+    /// Asks the runner to show a certain icon for the cursor.
+    pub cursor_icon: Option<CursorIcon>,
+}
+
+impl EventResponse {
+    /// A response for an element which did something with the event.
+    pub fn handled() -> Self {
+        Self {
+            handled: true,
+            ..Self::default()
+        }
+    }
+
+    /// A response for an element which did nothing with the event.
+    pub fn ignored() -> Self {
+        Self::default()
+    }
+
+    /// Adapts this response so that the event is not shown to elements after this one.
+    pub fn with_stop_propagation(self) -> Self {
+        Self {
+            stop_propagation: true,
+            ..self
+        }
+    }
+
+    /// Adapts this response to capture or release a pointer device.
+    pub fn with_pointer_capture(self, pointer_capture: PointerCapture) -> Self {
+        Self {
+            pointer_capture: Some(pointer_capture),
+            ..self
+        }
+    }
+
+    /// Adapts this response to ask for focus to be given to some item.
+    pub fn with_focus_request(self, focus: FocusId) -> Self {
+        Self {
+            request_focus: Some(focus),
+            ..self
+        }
+    }
+
+    /// Adapts this response to ask for a redraw.
+    pub fn with_redraw_request(self) -> Self {
+        Self {
+            request_redraw: true,
+            ..self
+        }
+    }
+
+    /// Adapts this response to ask for a certain cursor icon.
+    pub fn with_cursor_icon(self, cursor_icon: CursorIcon) -> Self {
+        Self {
+            cursor_icon: Some(cursor_icon),
+            ..self
+        }
+    }
+}
+
+impl From<bool> for EventResponse {
+    fn from(handled: bool) -> Self {
+        Self {
+            handled,
+            ..Self::default()
+        }
+    }
+}
+
+impl Semigroup for EventResponse {
+    /// Combines two responses, where requests from the first one take precedence.
+    fn combine(self, other: Self) -> Self {
+        Self {
+            handled: self.handled || other.handled,
+            stop_propagation: self.stop_propagation || other.stop_propagation,
+            pointer_capture: self.pointer_capture.or(other.pointer_capture),
+            request_focus: self.request_focus.or(other.request_focus),
+            request_redraw: self.request_redraw || other.request_redraw,
+            cursor_icon: self.cursor_icon.or(other.cursor_icon),
+        }
+    }
+}
+
+impl Empty for EventResponse {
+    fn empty() -> Self {
+        Self::default()
+    }
+}
+
+impl Propagate for EventResponse {
+    fn stops_propagation(&self) -> bool {
+        self.stop_propagation
+    }
+}
+
+/// A request to route all events of a pointer device to the element
+/// that captured it, even when the pointer leaves its bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerCapture {
+    Capture(DeviceId),
+    Release(DeviceId),
+}
+
+/// Identifies an item that can receive keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FocusId(pub u64);
+
+/// The icon a runner should show for the cursor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CursorIcon {
+    /// Whatever the platform's usual cursor looks like.
+    #[default]
+    Default,
+    /// Usually a hand, used for links and buttons.
+    Pointer,
+    /// Usually an I-beam, used for editable text.
+    Text,
+    /// Used for things that can be picked up.
+    Grab,
+    /// Used for things that are being dragged.
+    Grabbing,
+    /// Used for things that can be moved in any direction.
+    Move,
+    /// Used for things that can be resized horizontally.
+    EwResize,
+    /// Used for things that can be resized vertically.
+    NsResize,
+    /// Used for precise selection.
+    Crosshair,
+    /// The requested action can not be done.
+    NotAllowed,
+    /// The app is busy.
+    Wait,
+}
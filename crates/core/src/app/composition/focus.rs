@@ -0,0 +1,448 @@
+//! # Focus
+//!
+//! Keyboard events are not about any position on the screen, so, unlike cursor events,
+//! there's no natural way of telling which element they are meant for.
+//! Instead, at most one item at a time has _focus_, and items which care about the keyboard
+//! (text inputs, buttons, etc.) only react to it while they are focused.
+//!
+//! Items join a [`FocusScope`] by calling [`FocusScope::focusable`], and can then ask
+//! whether they have focus through the returned [`Focusable`] handle.
+//!
+//! The scope also takes care of moving focus around: wrapping a blueprint in a
+//! [`FocusScopeBlueprint`] makes `Tab` and `Shift + Tab` move focus to the next and previous
+//! items, in the order the current locale writes in (see [`CurrentFlow`]).
+//!
+//! ```rust,ignore
+//! let scope = FocusScope::new();
+//! let name = scope.focusable();
+//! let email = scope.focusable();
+//!
+//! // Both inputs share the keyboard, but only the focused one receives keys.
+//! let app = (
+//!     Typing::new(name_state).with_focus(name.clone()),
+//!     Typing::new(email_state).with_focus(email),
+//! );
+//!
+//! // Focus can also be moved programmatically.
+//! let mut focus_name = name.focus_effect();
+//! focus_name.apply();
+//! ```
+
+use crate::app::composition::algebra::{Bubble, Semigroup as _};
+use crate::app::composition::elements::response::EventResponse;
+use crate::app::composition::elements::{Blueprint, Element, Environment};
+use core::sync::atomic::{self, AtomicU64};
+use futures_signals::signal::{Mutable, Signal, SignalExt as _};
+use pin_project::pin_project;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use ui_composer_input::event::{
+    ButtonState, Event, KeyEvent, KeyboardEvent, Modifiers, NamedKey,
+};
+use ui_composer_math::flow::CurrentFlow;
+use ui_composer_math::prelude::{Rect, Vector2};
+use ui_composer_state::effect::Effect;
+
+pub use crate::app::composition::elements::response::FocusId;
+
+/// Ids are unique across scopes, so that a focus request
+/// can safely travel through nested scopes.
+static NEXT_FOCUS_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A group of items among which keyboard focus moves.
+///
+/// This is a cheap handle, clones of it refer to the same scope.
+#[derive(Clone, Default)]
+pub struct FocusScope {
+    focused: Mutable<Option<FocusId>>,
+    registry: Arc<Mutex<FocusRegistry>>,
+}
+
+#[derive(Default)]
+struct FocusRegistry {
+    flow: Option<CurrentFlow>,
+    entries: Vec<FocusEntry>,
+}
+
+struct FocusEntry {
+    id: FocusId,
+    rect: Option<Rect>,
+}
+
+impl FocusScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a new item that can receive focus in this scope.
+    ///
+    /// The item stays registered until the returned handle and all of its clones are dropped.
+    pub fn focusable(&self) -> Focusable {
+        let id = FocusId(NEXT_FOCUS_ID.fetch_add(1, atomic::Ordering::Relaxed));
+        self.registry().entries.push(FocusEntry { id, rect: None });
+
+        Focusable {
+            registration: Arc::new(Registration {
+                id,
+                scope: self.clone(),
+            }),
+        }
+    }
+
+    /// The item which currently has focus, if any.
+    pub fn focused(&self) -> Option<FocusId> {
+        self.focused.get()
+    }
+
+    /// A signal of the item which currently has focus.
+    pub fn focused_signal(
+        &self,
+    ) -> impl Signal<Item = Option<FocusId>> + Send + Sync + use<> {
+        self.focused.signal()
+    }
+
+    /// Whether an item was registered in this scope.
+    pub fn contains(&self, id: FocusId) -> bool {
+        self.registry().entries.iter().any(|entry| entry.id == id)
+    }
+
+    /// Gives focus to an item of this scope.
+    ///
+    /// Ids of items from other scopes are ignored.
+    pub fn focus(&self, id: FocusId) {
+        if self.contains(id) {
+            self.focused.set_neq(Some(id));
+        }
+    }
+
+    /// Takes focus away from whichever item has it.
+    pub fn blur(&self) {
+        self.focused.set_neq(None);
+    }
+
+    /// Moves focus to the next item, wrapping around at the end.
+    pub fn focus_next(&self) {
+        self.step(1);
+    }
+
+    /// Moves focus to the previous item, wrapping around at the start.
+    pub fn focus_previous(&self) {
+        self.step(-1);
+    }
+
+    /// An effect that moves focus to the next item.
+    pub fn focus_next_effect(&self) -> impl Effect + use<> {
+        let scope = self.clone();
+        move || scope.focus_next()
+    }
+
+    /// An effect that moves focus to the previous item.
+    pub fn focus_previous_effect(&self) -> impl Effect + use<> {
+        let scope = self.clone();
+        move || scope.focus_previous()
+    }
+
+    /// An effect that takes focus away from whichever item has it.
+    pub fn blur_effect(&self) -> impl Effect + use<> {
+        let scope = self.clone();
+        move || scope.blur()
+    }
+
+    /// Sets the flow focus order follows.
+    ///
+    /// This is usually done by the layout container that owns the scope.
+    pub fn set_flow(&self, flow: CurrentFlow) {
+        self.registry().flow = Some(flow);
+    }
+
+    /// Registered items in the order focus moves through them.
+    ///
+    /// Items are sorted along the writing cross-axis (lines), then along the writing axis,
+    /// by the center of their rect. Items that never reported a rect come last,
+    /// in the order they were registered.
+    pub fn order(&self) -> Vec<FocusId> {
+        let registry = self.registry();
+
+        let mut placed: Vec<(FocusId, f32, f32)> = Vec::new();
+        let mut unplaced: Vec<FocusId> = Vec::new();
+
+        for entry in registry.entries.iter() {
+            match (entry.rect, registry.flow) {
+                (Some(rect), Some(flow)) => {
+                    let center = Vector2::<f32>::new(
+                        rect.origin.x + rect.size.width / 2.0,
+                        rect.origin.y + rect.size.height / 2.0,
+                    );
+                    let along =
+                        |axis: Vector2| center.x * axis.x + center.y * axis.y;
+                    placed.push((
+                        entry.id,
+                        along(flow.writing_cross_axis()),
+                        along(flow.writing_axis()),
+                    ));
+                }
+                (Some(rect), None) => {
+                    placed.push((entry.id, rect.origin.y, rect.origin.x))
+                }
+                (None, _) => unplaced.push(entry.id),
+            }
+        }
+
+        // The sort is stable, so items at the same place keep their registration order.
+        placed.sort_by(|(_, line_a, axis_a), (_, line_b, axis_b)| {
+            line_a
+                .total_cmp(line_b)
+                .then_with(|| axis_a.total_cmp(axis_b))
+        });
+
+        placed
+            .into_iter()
+            .map(|(id, _, _)| id)
+            .chain(unplaced)
+            .collect()
+    }
+
+    fn step(&self, offset: isize) {
+        let order = self.order();
+        if order.is_empty() {
+            return;
+        }
+
+        let len = order.len() as isize;
+        let current = self
+            .focused()
+            .and_then(|id| order.iter().position(|other| *other == id));
+
+        let next = match current {
+            Some(index) => (index as isize + offset).rem_euclid(len),
+            // With nothing focused, moving forward starts at the first item,
+            // and moving backward starts at the last.
+            None if offset >= 0 => 0,
+            None => len - 1,
+        };
+
+        self.focused.set_neq(Some(order[next as usize]));
+    }
+
+    fn set_rect(&self, id: FocusId, rect: Rect) {
+        if let Some(entry) = self
+            .registry()
+            .entries
+            .iter_mut()
+            .find(|entry| entry.id == id)
+        {
+            entry.rect = Some(rect);
+        }
+    }
+
+    fn unregister(&self, id: FocusId) {
+        self.registry().entries.retain(|entry| entry.id != id);
+        if self.focused() == Some(id) {
+            self.blur();
+        }
+    }
+
+    fn registry(&self) -> MutexGuard<'_, FocusRegistry> {
+        self.registry
+            .lock()
+            .expect("The focus scope's lock was poisoned.")
+    }
+}
+
+/// An item registered in a [`FocusScope`].
+///
+/// Interactors that opt into focus hold one of these
+/// and only react to the keyboard while it [is focused](Focusable::is_focused).
+///
+/// Clones refer to the same item, which leaves its scope once they are all dropped.
+#[derive(Clone)]
+pub struct Focusable {
+    registration: Arc<Registration>,
+}
+
+/// Keeps an item registered in its scope for as long as it lives.
+struct Registration {
+    id: FocusId,
+    scope: FocusScope,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.scope.unregister(self.id);
+    }
+}
+
+impl Focusable {
+    pub fn id(&self) -> FocusId {
+        self.registration.id
+    }
+
+    /// The scope this item belongs to.
+    pub fn scope(&self) -> &FocusScope {
+        &self.registration.scope
+    }
+
+    /// Whether this item currently has focus.
+    pub fn is_focused(&self) -> bool {
+        self.scope().focused() == Some(self.id())
+    }
+
+    /// A signal of whether this item has focus, useful for drawing focus rings and such.
+    pub fn is_focused_signal(
+        &self,
+    ) -> impl Signal<Item = bool> + Send + Sync + use<> {
+        let id = self.id();
+        self.scope()
+            .focused_signal()
+            .map(move |focused| focused == Some(id))
+            .dedupe()
+    }
+
+    /// Gives focus to this item.
+    pub fn focus(&self) {
+        self.scope().focus(self.id())
+    }
+
+    /// Takes focus away from this item, if it has it.
+    pub fn blur(&self) {
+        if self.is_focused() {
+            self.scope().blur();
+        }
+    }
+
+    /// Tells the scope where this item is, so it can be put in the right place in focus order.
+    pub fn set_rect(&self, rect: Rect) {
+        self.scope().set_rect(self.id(), rect)
+    }
+
+    /// An effect that gives focus to this item.
+    pub fn focus_effect(&self) -> impl Effect + use<> {
+        let focusable = self.clone();
+        move || focusable.focus()
+    }
+
+    /// An effect that takes focus away from this item.
+    pub fn blur_effect(&self) -> impl Effect + use<> {
+        let focusable = self.clone();
+        move || focusable.blur()
+    }
+}
+
+/// A blueprint whose element moves focus around a [`FocusScope`].
+///
+/// Its element shows events to its content first. Then:
+/// - `request_focus`s in the content's response for items of the scope are fulfilled;
+/// - `Tab` and `Shift + Tab` presses the content did not handle move focus forward and backward.
+pub struct FocusScopeBlueprint<A> {
+    scope: FocusScope,
+    blueprint: A,
+}
+
+impl<A> FocusScopeBlueprint<A> {
+    pub fn new(scope: FocusScope, blueprint: A) -> Self {
+        Self { scope, blueprint }
+    }
+}
+
+impl<Env, A> Blueprint<Env> for FocusScopeBlueprint<A>
+where
+    Env: Environment,
+    A: Blueprint<Env>,
+{
+    type Element = FocusScopeElement<A::Element>;
+
+    fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
+        FocusScopeElement {
+            scope: self.scope,
+            modifiers: Modifiers::default(),
+            element: self.blueprint.make(env),
+        }
+    }
+
+    fn reconcile(
+        self,
+        element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) {
+        let element = element.project();
+        *element.scope = self.scope;
+        self.blueprint.reconcile(element.element, env);
+    }
+}
+
+/// The element made by a [`FocusScopeBlueprint`].
+#[pin_project]
+pub struct FocusScopeElement<E> {
+    scope: FocusScope,
+    modifiers: Modifiers,
+    #[pin]
+    element: E,
+}
+
+impl<E> Bubble<Event, EventResponse> for FocusScopeElement<E>
+where
+    E: Bubble<Event, EventResponse>,
+{
+    fn bubble(&mut self, event: &mut Event) -> EventResponse {
+        if let Event::Keyboard {
+            event: KeyboardEvent::ModifiersChanged(modifiers),
+            ..
+        } = event
+        {
+            self.modifiers = *modifiers;
+        }
+
+        let response = self.element.bubble(event);
+
+        if let Some(id) = response.request_focus
+            && self.scope.contains(id)
+        {
+            self.scope.focus(id);
+        }
+
+        if !response.handled
+            && let Event::Keyboard {
+                event:
+                    KeyboardEvent::Key(KeyEvent {
+                        named_key: Some(NamedKey::Tab),
+                        button_state: ButtonState::Pressed,
+                        ..
+                    }),
+                ..
+            } = event
+        {
+            if self.modifiers.shift {
+                self.scope.focus_previous();
+            } else {
+                self.scope.focus_next();
+            }
+            return response.combine(EventResponse::handled());
+        }
+
+        response
+    }
+}
+
+impl<Env, E> Element<Env> for FocusScopeElement<E>
+where
+    Env: Environment,
+    E: Element<Env>,
+{
+    type Effect<'fx>
+        = E::Effect<'fx>
+    where
+        Self: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        self.element.effect()
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        self.project().element.poll(cx, env)
+    }
+}
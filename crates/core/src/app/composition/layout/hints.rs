@@ -11,9 +11,15 @@
 //!
 //! [`ParentHints`] is that context.
 //!
-//! By default, layout stuff is passed through it, though there are plans of allowing the user
-//! to pass anything they want (by introducing a generic parameter). This isn't available yet
-//! because, as of now, it would be incredibly annoying to use. Man, generics are so hard to keep tidy.
+//! By default, layout stuff is passed through it, along with a [`CascadingContext`]:
+//! things like the theme, the text color or whether the UI is disabled.
+//! Containers can override the context for their subtree, and leaf items read it in
+//! [`LayoutItem::place`], so, for example, a button can tell the label inside it
+//! which color its text should be, without the label knowing anything about buttons.
+//!
+//! Apps can cascade their own data too, keyed by its type, with
+//! [`CascadingContext::with_value`] and [`CascadingContext::value`].
+//! Values are shared, so cloning the context down a layout is cheap.
 //!
 //! [`ChildHints`] is a bundle of context a child might reply to the parent with. It might
 //! contain information like minimum size, natural size, etc., which are useful for layout calculations.
@@ -24,20 +30,259 @@
 //! 2. Perform layout calculations;
 //! 3. Generate parent hints, likely in order, while calling [`LayoutItem::lay`] on them.;
 //!
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::Debug,
+    sync::Arc,
+};
+
+use ui_composer_input::event::ThemeType;
 use ui_composer_math::{
     flow::CurrentFlow,
-    prelude::{Rect, Size2},
+    prelude::{Rect, Size2, Srgba},
 };
 
 /// The parent hints struct.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ParentHints {
     pub rect: Rect,
     pub current_flow: CurrentFlow,
+    pub context: CascadingContext,
+}
+
+/// Context that cascades down a layout, which containers can override for their subtree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CascadingContext {
+    /// Whether the UI should look light or dark.
+    pub theme: ThemeType,
+    /// The color text should have, overriding whatever color it would have otherwise.
+    pub text_color: Option<Srgba>,
+    /// How much bigger than usual text should be.
+    pub font_scale: f32,
+    /// The locale text is written in.
+    pub locale: Locale,
+    /// Whether interactive items should ignore the user.
+    pub disabled: bool,
+    /// Whatever else the app wants to cascade, keyed by its type.
+    values: CascadingValues,
+}
+
+impl Default for CascadingContext {
+    fn default() -> Self {
+        Self {
+            theme: ThemeType::Light,
+            text_color: None,
+            font_scale: 1.0,
+            locale: Locale::default(),
+            disabled: false,
+            values: CascadingValues::default(),
+        }
+    }
+}
+
+impl CascadingContext {
+    pub fn with_theme(self, theme: ThemeType) -> Self {
+        Self { theme, ..self }
+    }
+
+    pub fn with_text_color(self, text_color: Srgba) -> Self {
+        Self {
+            text_color: Some(text_color),
+            ..self
+        }
+    }
+
+    pub fn with_font_scale(self, font_scale: f32) -> Self {
+        Self { font_scale, ..self }
+    }
+
+    pub fn with_locale(self, locale: Locale) -> Self {
+        Self { locale, ..self }
+    }
+
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, ..self }
+    }
+
+    /// Cascades `value` down, replacing any value of the same type.
+    pub fn with_value<T>(mut self, value: T) -> Self
+    where
+        T: Debug + PartialEq + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.values.0)
+            .insert(TypeId::of::<T>(), Arc::new(value));
+        self
+    }
+
+    /// The value of type `T` cascaded down by some ancestor, if any.
+    pub fn value<T: 'static>(&self) -> Option<&T> {
+        self.values
+            .0
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.as_any().downcast_ref())
+    }
+}
+
+/// The app's own values in a [`CascadingContext`].
+#[derive(Debug, Clone, Default)]
+struct CascadingValues(Arc<HashMap<TypeId, Arc<dyn CascadingValue>>>);
+
+impl PartialEq for CascadingValues {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+            || self.0.len() == other.0.len()
+                && self.0.iter().all(|(key, value)| {
+                    other.0.get(key).is_some_and(|other| {
+                        Arc::ptr_eq(value, other) || value.dyn_eq(&**other)
+                    })
+                })
+    }
+}
+
+trait CascadingValue: Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn dyn_eq(&self, other: &dyn CascadingValue) -> bool;
+}
+
+impl<T> CascadingValue for T
+where
+    T: Debug + PartialEq + Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn dyn_eq(&self, other: &dyn CascadingValue) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
+/// A language tag, like `en-US` or `pt-BR`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Locale(pub &'static str);
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self("en-US")
+    }
 }
 
 /// The child hints struct.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct ChildHints {
+    /// The size below which the item can not be drawn properly.
     pub minimum_size: Size2,
+    /// The size the item would naturally have, if there's room for it.
+    /// If `None`, the item is happy with its minimum size.
+    pub preferred_size: Option<Size2>,
+    /// The size past which the item should not grow.
+    /// If `None`, the item can grow indefinitely.
+    pub maximum_size: Option<Size2>,
+    /// The ratio (width / height) the item wants to keep.
+    pub aspect_ratio: Option<f32>,
+}
+
+impl ChildHints {
+    pub fn new(minimum_size: Size2) -> Self {
+        Self {
+            minimum_size,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_minimum_size(self, minimum_size: Size2) -> Self {
+        Self {
+            minimum_size,
+            ..self
+        }
+    }
+
+    pub fn with_preferred_size(self, preferred_size: Size2) -> Self {
+        Self {
+            preferred_size: Some(preferred_size),
+            ..self
+        }
+    }
+
+    pub fn with_maximum_size(self, maximum_size: Size2) -> Self {
+        Self {
+            maximum_size: Some(maximum_size),
+            ..self
+        }
+    }
+
+    pub fn with_aspect_ratio(self, aspect_ratio: f32) -> Self {
+        Self {
+            aspect_ratio: Some(aspect_ratio),
+            ..self
+        }
+    }
+
+    /// These hints, with the minimum height replaced by one measured
+    /// for a known width (see [`LayoutItem::height_for_width`]).
+    ///
+    /// [`LayoutItem::height_for_width`]: crate::app::composition::layout::LayoutItem::height_for_width
+    pub fn with_measured_height(self, height: f32) -> Self {
+        Self {
+            minimum_size: Size2::new(self.minimum_size.width, height),
+            ..self
+        }
+    }
+
+    /// Brings a size within this item's minimum and maximum sizes.
+    /// The minimum size wins if the two disagree.
+    pub fn clamp(&self, size: Size2) -> Size2 {
+        let size = match self.maximum_size {
+            Some(maximum_size) => size.min(maximum_size),
+            None => size,
+        };
+        size.max(self.minimum_size)
+    }
+
+    /// The size this item would like to have.
+    pub fn natural_size(&self) -> Size2 {
+        self.fit(self.preferred_size.unwrap_or(self.minimum_size))
+    }
+
+    /// The biggest size, within `available`, that respects this item's
+    /// maximum size and aspect ratio. It is never smaller than the minimum size.
+    pub fn fit(&self, available: Size2) -> Size2 {
+        let mut size = self.clamp(available);
+
+        if let Some(aspect_ratio) = self.aspect_ratio
+            && aspect_ratio > 0.0
+            && (size.width.is_finite() || size.height.is_finite())
+        {
+            if size.width > size.height * aspect_ratio {
+                size.width = size.height * aspect_ratio;
+            } else {
+                size.height = size.width / aspect_ratio;
+            }
+            size = size.max(self.minimum_size);
+        }
+
+        size
+    }
+
+    /// Hints for two items drawn on top of each other, in the same rect.
+    pub fn overlay(self, other: Self) -> Self {
+        let minimum_size = self.minimum_size.max(other.minimum_size);
+
+        Self {
+            minimum_size,
+            preferred_size: match (self.preferred_size, other.preferred_size) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            },
+            maximum_size: match (self.maximum_size, other.maximum_size) {
+                (Some(a), Some(b)) => Some(a.min(b).max(minimum_size)),
+                (a, b) => a.or(b).map(|size| size.max(minimum_size)),
+            },
+            aspect_ratio: match (self.aspect_ratio, other.aspect_ratio) {
+                (Some(a), Some(b)) if a != b => None,
+                (a, b) => a.or(b),
+            },
+        }
+    }
 }
@@ -7,12 +7,14 @@ impl LayoutItem for () {
     type Blueprint = ();
 
     fn prepare(&mut self, _: ParentHints) -> ChildHints {
-        ChildHints {
-            minimum_size: Size2::ZERO,
-        }
+        ChildHints::new(Size2::ZERO)
     }
 
     fn place(&mut self, _: ParentHints) -> Self::Blueprint {}
+
+    fn height_for_width(&mut self, _: f32, _: ParentHints) -> f32 {
+        0.0
+    }
 }
 
 impl<A, B> LayoutItem for (A, B)
@@ -26,15 +28,26 @@ where
         &mut self,
         parent_hints: ParentHints,
     ) -> crate::app::composition::layout::hints::ChildHints {
-        let a = self.0.prepare(parent_hints);
-        let b = self.0.prepare(parent_hints);
-        ChildHints {
-            minimum_size: a.minimum_size.max(b.minimum_size),
-        }
+        let a = self.0.prepare(parent_hints.clone());
+        let b = self.1.prepare(parent_hints);
+        a.overlay(b)
     }
 
     fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
-        (self.0.place(parent_hints), self.1.place(parent_hints))
+        (
+            self.0.place(parent_hints.clone()),
+            self.1.place(parent_hints),
+        )
+    }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        let a = self.0.height_for_width(width, parent_hints.clone());
+        let b = self.1.height_for_width(width, parent_hints);
+        a.max(b)
     }
 }
 
@@ -51,4 +64,12 @@ where
     fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
         self.as_mut().place(parent_hints)
     }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        self.as_mut().height_for_width(width, parent_hints)
+    }
 }
@@ -0,0 +1,210 @@
+//! # Memoization
+//!
+//! Laying out is a pure function of the [`ParentHints`] an item receives... as long as
+//! the item itself doesn't change. Containers, however, will happily lay out their whole subtree
+//! again whenever asked to, even if nothing in it changed — for example, a sidebar of fixed width
+//! doesn't care that the window got taller, but it's laid out again on every resize.
+//!
+//! Wrapping an item in a [`Memo`] (see [`LayoutItem::memo`]) makes it remember its last results
+//! and reuse them while it's asked about the same hints again.
+//!
+//! ## Invalidation
+//!
+//! If the item's inputs can change from the outside (i.e. it's built from some shared state),
+//! give the memo an [`Invalidator`] and [invalidate](Invalidator::invalidate) it when they do.
+//!
+//! Memos can't see through each other, so an outer memo would keep returning results made
+//! with the stale inner ones. Give outer memos the [parent](Invalidator::child) of the
+//! invalidators of inner memos, so that invalidating an inner memo also invalidates the outer ones.
+//!
+//! ```rust,ignore
+//! let page = Invalidator::new();
+//! let sidebar = page.child();
+//!
+//! let app = row((
+//!     stacked(sidebar_items(&state).memo().with_invalidator(sidebar.clone())),
+//!     stacked(content()),
+//! ))
+//! .memo()
+//! .with_invalidator(page);
+//!
+//! // Relays out the sidebar and the row, but not the content.
+//! sidebar.invalidate();
+//! ```
+
+use crate::app::composition::layout::LayoutItem;
+use crate::app::composition::layout::hints::{ChildHints, ParentHints};
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use ui_composer_state::effect::Effect;
+
+/// A handle that tells [`Memo`]s their item changed, see the [module docs](self).
+///
+/// This is a cheap handle, clones of it refer to the same invalidator.
+#[derive(Debug, Clone, Default)]
+pub struct Invalidator {
+    node: Arc<InvalidatorNode>,
+}
+
+#[derive(Debug, Default)]
+struct InvalidatorNode {
+    version: AtomicU64,
+    parent: Option<Arc<InvalidatorNode>>,
+}
+
+impl Invalidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new invalidator which, when invalidated, also invalidates this one.
+    pub fn child(&self) -> Self {
+        Self {
+            node: Arc::new(InvalidatorNode {
+                version: AtomicU64::new(0),
+                parent: Some(self.node.clone()),
+            }),
+        }
+    }
+
+    /// Makes memos using this invalidator (or any of its ancestors) lay out their items again.
+    pub fn invalidate(&self) {
+        let mut node = Some(&self.node);
+        while let Some(current) = node {
+            current.version.fetch_add(1, Ordering::Relaxed);
+            node = current.parent.as_ref();
+        }
+    }
+
+    /// An effect that invalidates this invalidator.
+    pub fn invalidate_effect(&self) -> impl Effect + use<> {
+        let invalidator = self.clone();
+        move || invalidator.invalidate()
+    }
+
+    /// How many times this invalidator was invalidated so far.
+    fn version(&self) -> u64 {
+        self.node.version.load(Ordering::Relaxed)
+    }
+}
+
+/// A layout item that remembers the results of laying out its item,
+/// see the [module docs](self).
+///
+/// Results of [`LayoutItem::place`] are cloned out of the memo,
+/// so the item's blueprint must be [`Clone`].
+pub struct Memo<A: LayoutItem> {
+    item: A,
+    invalidator: Option<Invalidator>,
+    /// The hints the item itself was last prepared with.
+    /// Items often keep some state between `prepare` and `place`,
+    /// so this tells whether the item must be prepared again before placing it.
+    last_prepared: Option<MemoKey>,
+    prepared: Option<(MemoKey, ChildHints)>,
+    placed: Option<(MemoKey, A::Blueprint)>,
+    measured: Option<(MemoKey, f32, f32)>,
+}
+
+/// What laying out an item depends on.
+#[derive(Clone, PartialEq)]
+struct MemoKey {
+    parent_hints: ParentHints,
+    version: u64,
+}
+
+impl<A: LayoutItem> Memo<A> {
+    pub fn new(item: A) -> Self {
+        Self {
+            item,
+            invalidator: None,
+            last_prepared: None,
+            prepared: None,
+            placed: None,
+            measured: None,
+        }
+    }
+
+    /// Makes this memo forget its results whenever `invalidator` is invalidated.
+    pub fn with_invalidator(self, invalidator: Invalidator) -> Self {
+        Self {
+            invalidator: Some(invalidator),
+            ..self
+        }
+    }
+
+    /// Forgets every result, so that the item is laid out again next time.
+    pub fn clear(&mut self) {
+        self.last_prepared = None;
+        self.prepared = None;
+        self.placed = None;
+        self.measured = None;
+    }
+
+    fn key(&self, parent_hints: ParentHints) -> MemoKey {
+        MemoKey {
+            parent_hints,
+            version: self.invalidator.as_ref().map_or(0, Invalidator::version),
+        }
+    }
+}
+
+impl<A> LayoutItem for Memo<A>
+where
+    A: LayoutItem,
+    A::Blueprint: Clone + Send,
+{
+    type Blueprint = A::Blueprint;
+
+    fn prepare(&mut self, parent_hints: ParentHints) -> ChildHints {
+        let key = self.key(parent_hints.clone());
+        if let Some((cached_key, hints)) = &self.prepared
+            && *cached_key == key
+        {
+            return *hints;
+        }
+
+        let hints = self.item.prepare(parent_hints);
+        self.last_prepared = Some(key.clone());
+        self.prepared = Some((key, hints));
+        hints
+    }
+
+    fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
+        let key = self.key(parent_hints.clone());
+        if let Some((cached_key, blueprint)) = &self.placed
+            && *cached_key == key
+        {
+            return blueprint.clone();
+        }
+
+        if self.last_prepared.as_ref() != Some(&key) {
+            let hints = self.item.prepare(parent_hints.clone());
+            self.last_prepared = Some(key.clone());
+            self.prepared = Some((key.clone(), hints));
+        }
+
+        let blueprint = self.item.place(parent_hints);
+        self.placed = Some((key, blueprint.clone()));
+        blueprint
+    }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        let key = self.key(parent_hints.clone());
+        if let Some((cached_key, cached_width, height)) = &self.measured
+            && *cached_key == key
+            && *cached_width == width
+        {
+            return *height;
+        }
+
+        // Measuring might prepare the item with other hints.
+        let height = self.item.height_for_width(width, parent_hints);
+        self.last_prepared = None;
+        self.measured = Some((key, width, height));
+        height
+    }
+}
@@ -66,10 +66,14 @@ use crate::app::composition::effects::signal::{IntoBlueprint as _, React};
 use crate::app::composition::elements::{Blueprint, Environment};
 use futures_signals::signal::{Signal, SignalExt};
 use hints::{ChildHints, ParentHints};
+use memo::Memo;
+use relayout::Relayout;
 use ui_composer_math::prelude::{Rect, Size2};
 
 pub mod hints;
 mod implementations;
+pub mod memo;
+pub mod relayout;
 
 /// The closure-like trait that produces [`Emit`]s.
 #[diagnostic::on_unimplemented(
@@ -91,6 +95,34 @@ pub trait LayoutItem: Send {
         parent_hints: ParentHints,
     ) -> Self::Blueprint;
 
+    /// Measures how tall this item needs to be when it is given `width`.
+    ///
+    /// Most items need the same height no matter their width, which is what
+    /// the default implementation assumes. Wrapping text, however, needs fewer lines
+    /// the wider it is, so the minimum height in its [`ChildHints`] can only be a guess.
+    /// Containers that know how wide an item will be should ask it this instead,
+    /// and containers that wrap another item should forward the question to it.
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        expected_parent_hints: ParentHints,
+    ) -> f32 {
+        let hints = self.prepare(ParentHints {
+            rect: Rect::new(
+                expected_parent_hints.rect.origin,
+                Size2::new(width, expected_parent_hints.rect.size.height),
+            ),
+            ..expected_parent_hints
+        });
+
+        match hints.aspect_ratio {
+            Some(aspect_ratio) if aspect_ratio > 0.0 => {
+                hints.minimum_size.height.max(width / aspect_ratio)
+            }
+            _ => hints.minimum_size.height,
+        }
+    }
+
     /// Creates a reactive Element that resizes its content to fit `rect_signal`.
     fn place_reactive<Sig, Env: Environment>(
         mut self,
@@ -106,12 +138,37 @@ pub trait LayoutItem: Send {
             .map(move |rect| {
                 self.place(ParentHints {
                     rect,
-                    ..parent_hints
+                    ..parent_hints.clone()
                 })
             })
             .into_blueprint()
     }
 
+    /// Wraps this item in a [`Memo`], which remembers the results of laying it out
+    /// so that it's not laid out again with the same hints.
+    ///
+    /// See the [`memo`] module for when and how to invalidate those results.
+    fn memo(self) -> Memo<Self>
+    where
+        Self: Sized,
+        Self::Blueprint: Clone + Send,
+    {
+        Memo::new(self)
+    }
+
+    /// Wraps this item in a [`Relayout`], which lays it out again
+    /// whenever the signal created by `changes` fires.
+    ///
+    /// See the [`relayout`] module for when that is needed.
+    fn relayout_on<F, Sig>(self, changes: F) -> Relayout<Self, F>
+    where
+        Self: Sized,
+        F: FnMut() -> Sig,
+        Sig: Signal<Item = ()>,
+    {
+        Relayout::new(self, changes)
+    }
+
     /// Erases the type of the layout item, allocating it on the heap,
     /// while remembering the type of `Blueprint` the item generates.
     ///
@@ -143,6 +200,16 @@ pub trait LayoutItem: Send {
 pub trait Resizable: LayoutItem {
     /// Consumes this [`ItemBox`] and returns a similar one with the minimum size set.
     fn with_minimum_size(self, min_size: Size2) -> Self;
+
+    /// Consumes this [`ItemBox`] and returns a similar one with the preferred size set.
+    fn with_preferred_size(self, preferred_size: Size2) -> Self;
+
+    /// Consumes this [`ItemBox`] and returns a similar one with the maximum size set.
+    fn with_maximum_size(self, max_size: Size2) -> Self;
+
+    /// Consumes this [`ItemBox`] and returns a similar one
+    /// that keeps a certain ratio (width / height).
+    fn with_aspect_ratio(self, aspect_ratio: f32) -> Self;
 }
 
 pub struct ItemBox<Factory, Item>
@@ -186,9 +253,28 @@ where
 {
     fn with_minimum_size(self, min_size: Size2) -> Self {
         Self {
-            hints: ChildHints {
-                minimum_size: min_size,
-            },
+            hints: self.hints.with_minimum_size(min_size),
+            ..self
+        }
+    }
+
+    fn with_preferred_size(self, preferred_size: Size2) -> Self {
+        Self {
+            hints: self.hints.with_preferred_size(preferred_size),
+            ..self
+        }
+    }
+
+    fn with_maximum_size(self, max_size: Size2) -> Self {
+        Self {
+            hints: self.hints.with_maximum_size(max_size),
+            ..self
+        }
+    }
+
+    fn with_aspect_ratio(self, aspect_ratio: f32) -> Self {
+        Self {
+            hints: self.hints.with_aspect_ratio(aspect_ratio),
             ..self
         }
     }
@@ -241,9 +327,28 @@ where
 {
     fn with_minimum_size(self, min_size: Size2) -> Self {
         Self {
-            hints: ChildHints {
-                minimum_size: min_size,
-            },
+            hints: self.hints.with_minimum_size(min_size),
+            ..self
+        }
+    }
+
+    fn with_preferred_size(self, preferred_size: Size2) -> Self {
+        Self {
+            hints: self.hints.with_preferred_size(preferred_size),
+            ..self
+        }
+    }
+
+    fn with_maximum_size(self, max_size: Size2) -> Self {
+        Self {
+            hints: self.hints.with_maximum_size(max_size),
+            ..self
+        }
+    }
+
+    fn with_aspect_ratio(self, aspect_ratio: f32) -> Self {
+        Self {
+            hints: self.hints.with_aspect_ratio(aspect_ratio),
             ..self
         }
     }
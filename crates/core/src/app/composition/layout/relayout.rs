@@ -0,0 +1,212 @@
+//! # Relayout
+//!
+//! Layout items are laid out when their container is, which usually happens when the window
+//! is resized. An item whose _content_ can change at runtime (e.g. a list of items backed by
+//! a `MutableVec`) needs to be laid out again when that happens, too.
+//!
+//! A [`Relayout`] (see [`LayoutItem::relayout_on`]) places a blueprint that lays its item out
+//! again, in the same rect, whenever a signal of changes fires, then
+//! [reconciles](Blueprint::reconcile) its element with the result.
+//!
+//! ```rust,ignore
+//! let todos = MutableVec::new();
+//!
+//! let list = flex(for_each(todos.clone(), |todo| item(TodoView(todo))))
+//!     .with_vertical_flow()
+//!     .relayout_on(move || todos.signal_vec_cloned().to_signal_map(|_| ()));
+//! ```
+
+use crate::app::composition::algebra::{Bubble, Semigroup as _};
+use crate::app::composition::elements::response::EventResponse;
+use crate::app::composition::elements::{Blueprint, Element, Environment};
+use crate::app::composition::layout::LayoutItem;
+use crate::app::composition::layout::hints::{ChildHints, ParentHints};
+use futures_signals::signal::Signal;
+use pin_project::pin_project;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use ui_composer_input::event::Event;
+
+/// A layout item that is laid out again whenever a signal fires,
+/// see the [module docs](self).
+pub struct Relayout<A, F> {
+    item: Arc<Mutex<A>>,
+    changes: F,
+}
+
+impl<A, F, Sig> Relayout<A, F>
+where
+    A: LayoutItem,
+    F: FnMut() -> Sig,
+    Sig: Signal<Item = ()>,
+{
+    /// `changes` is called each time the item is placed, creating a signal
+    /// that fires whenever the item should be laid out again.
+    pub fn new(item: A, changes: F) -> Self {
+        Self {
+            item: Arc::new(Mutex::new(item)),
+            changes,
+        }
+    }
+}
+
+impl<A, F, Sig> LayoutItem for Relayout<A, F>
+where
+    A: LayoutItem,
+    F: FnMut() -> Sig + Send,
+    Sig: Signal<Item = ()>,
+{
+    type Blueprint = RelayoutBlueprint<A, Sig>;
+
+    fn prepare(&mut self, parent_hints: ParentHints) -> ChildHints {
+        lock(&self.item).prepare(parent_hints)
+    }
+
+    fn place(&mut self, parent_hints: ParentHints) -> Self::Blueprint {
+        RelayoutBlueprint {
+            item: self.item.clone(),
+            blueprint: lock(&self.item).place(parent_hints.clone()),
+            parent_hints,
+            changes: (self.changes)(),
+        }
+    }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        lock(&self.item).height_for_width(width, parent_hints)
+    }
+}
+
+/// The blueprint placed by a [`Relayout`].
+pub struct RelayoutBlueprint<A: LayoutItem, Sig> {
+    item: Arc<Mutex<A>>,
+    parent_hints: ParentHints,
+    blueprint: A::Blueprint,
+    changes: Sig,
+}
+
+impl<A, Sig, Env> Blueprint<Env> for RelayoutBlueprint<A, Sig>
+where
+    Env: Environment,
+    A: LayoutItem,
+    A::Blueprint: Blueprint<Env>,
+    Sig: Signal<Item = ()>,
+{
+    type Element = RelayoutElement<A, Sig, Env>;
+
+    fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
+        RelayoutElement {
+            item: self.item,
+            parent_hints: self.parent_hints,
+            changes: self.changes,
+            skip_initial_change: true,
+            element: self.blueprint.make(env),
+        }
+    }
+
+    fn reconcile(
+        self,
+        element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) {
+        let mut element = element.project();
+        *element.item = self.item;
+        *element.parent_hints = self.parent_hints;
+        element.changes.set(self.changes);
+        *element.skip_initial_change = true;
+        self.blueprint.reconcile(element.element, env);
+    }
+}
+
+/// The element made by a [`RelayoutBlueprint`].
+#[pin_project]
+pub struct RelayoutElement<A, Sig, Env>
+where
+    Env: Environment,
+    A: LayoutItem,
+    A::Blueprint: Blueprint<Env>,
+{
+    item: Arc<Mutex<A>>,
+    parent_hints: ParentHints,
+    #[pin]
+    changes: Sig,
+    /// Signals fire with their current value first, but the item was just laid out.
+    skip_initial_change: bool,
+    #[pin]
+    element: <A::Blueprint as Blueprint<Env>>::Element,
+}
+
+impl<A, Sig, Env> Bubble<Event, EventResponse> for RelayoutElement<A, Sig, Env>
+where
+    Env: Environment,
+    A: LayoutItem,
+    A::Blueprint: Blueprint<Env>,
+{
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
+        self.element.bubble(cx)
+    }
+}
+
+impl<A, Sig, Env> Element<Env> for RelayoutElement<A, Sig, Env>
+where
+    Env: Environment,
+    A: LayoutItem,
+    A::Blueprint: Blueprint<Env>,
+    Sig: Signal<Item = ()>,
+{
+    type Effect<'fx>
+        =
+        <<A::Blueprint as Blueprint<Env>>::Element as Element<Env>>::Effect<'fx>
+    where
+        Self: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        self.element.effect()
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        let mut this = self.project();
+
+        let mut changes_poll = Poll::Pending;
+        loop {
+            match this.changes.as_mut().poll_change(cx) {
+                Poll::Ready(Some(())) if *this.skip_initial_change => {
+                    *this.skip_initial_change = false;
+                }
+                Poll::Ready(Some(())) => changes_poll = Poll::Ready(Some(())),
+                Poll::Ready(None) => {
+                    if changes_poll.is_pending() {
+                        changes_poll = Poll::Ready(None);
+                    }
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        if let Poll::Ready(Some(())) = changes_poll {
+            let parent_hints = this.parent_hints.clone();
+            let blueprint = {
+                let mut item = lock(this.item);
+                item.prepare(parent_hints.clone());
+                item.place(parent_hints)
+            };
+            blueprint.reconcile(this.element.as_mut(), env);
+        }
+
+        let element_poll = this.element.poll(cx, env);
+        changes_poll.combine(element_poll)
+    }
+}
+
+fn lock<A>(item: &Mutex<A>) -> std::sync::MutexGuard<'_, A> {
+    item.lock().expect("The relayout item's lock was poisoned.")
+}
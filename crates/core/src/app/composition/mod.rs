@@ -8,7 +8,9 @@ pub mod algebra;
 pub mod convert;
 pub mod effects;
 pub mod elements;
+pub mod focus;
 pub mod layout;
+pub mod pointer;
 pub mod visit;
 
 /// Trait for an item that can be used in an app's layout context.
@@ -0,0 +1,287 @@
+//! # Pointers
+//!
+//! Interactors like taps, hovers and drags all need to know where the cursor is,
+//! but cursor events only say where a cursor _moved to_, so each one of them would have to
+//! remember it on its own — and a freshly made element would have no idea where the cursor is
+//! until it moved again.
+//!
+//! Instead, a [`PointerScopeBlueprint`] keeps the state of every pointer device
+//! in a shared [`Pointers`] context, updating it before showing cursor events to its content.
+//! Interactors that hold a clone of the context can then read it at any time.
+//!
+//! ## Capture
+//!
+//! While a pointer is pressed on something like a slider, that thing should keep receiving
+//! the pointer even when it leaves its bounds, and nothing else should react to it.
+//! An interactor can [capture](Pointers::capture) a pointer to claim it,
+//! and other interactors should ignore pointers [captured](Pointers::is_captured_by_other)
+//! by someone else.
+//!
+//! Captures are released when the interactor says so, or when every button of the pointer is released.
+//! Interactors should also answer with [`PointerCapture`] in their [`EventResponse`],
+//! so that runners can keep the cursor inside the window while it's captured.
+
+use crate::app::composition::algebra::Bubble;
+use crate::app::composition::elements::response::{
+    EventResponse, PointerCapture,
+};
+use crate::app::composition::elements::{Blueprint, Element, Environment};
+use core::sync::atomic::{self, AtomicU64};
+use pin_project::pin_project;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use ui_composer_input::event::{ButtonState, CursorEvent, DeviceId, Event};
+use ui_composer_math::glamour::Contains;
+use ui_composer_math::prelude::{Point2, Rect};
+
+static NEXT_CAPTURE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Identifies something that can capture pointers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CaptureId(u64);
+
+impl CaptureId {
+    /// Creates a new id, different from every other one.
+    pub fn new() -> Self {
+        Self(NEXT_CAPTURE_ID.fetch_add(1, atomic::Ordering::Relaxed))
+    }
+}
+
+impl Default for CaptureId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What is known about a pointer device.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PointerState {
+    /// Where the pointer is, or `None` if it is outside the app.
+    pub position: Option<Point2>,
+    /// How many of its buttons are being held down.
+    pub pressed_buttons: u32,
+    /// Who captured this pointer, if anyone.
+    pub captured_by: Option<CaptureId>,
+}
+
+/// The shared state of every pointer device, see the [module docs](self).
+///
+/// This is a cheap handle, clones of it refer to the same state.
+#[derive(Debug, Clone, Default)]
+pub struct Pointers {
+    devices: Arc<Mutex<HashMap<DeviceId, PointerState>>>,
+}
+
+impl Pointers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The state of a pointer device.
+    pub fn get(&self, device: DeviceId) -> PointerState {
+        self.devices().get(&device).copied().unwrap_or_default()
+    }
+
+    /// Where a pointer device is.
+    pub fn position(&self, device: DeviceId) -> Option<Point2> {
+        self.get(device).position
+    }
+
+    /// Whether any pointer that was not captured by someone other than `by` is within `rect`.
+    pub fn any_within(&self, rect: Rect, by: CaptureId) -> bool {
+        self.devices().values().any(|pointer| {
+            pointer.captured_by.is_none_or(|captor| captor == by)
+                && pointer
+                    .position
+                    .is_some_and(|position| rect.contains(&position))
+        })
+    }
+
+    /// Captures a pointer device for `by`.
+    ///
+    /// Returns whether the capture succeeded, which only fails
+    /// if someone else already captured the device.
+    pub fn capture(&self, device: DeviceId, by: CaptureId) -> bool {
+        let mut devices = self.devices();
+        let pointer = devices.entry(device).or_default();
+
+        match pointer.captured_by {
+            Some(captor) if captor != by => false,
+            _ => {
+                pointer.captured_by = Some(by);
+                true
+            }
+        }
+    }
+
+    /// Releases a pointer device, if it was captured by `by`.
+    pub fn release(&self, device: DeviceId, by: CaptureId) {
+        if let Some(pointer) = self.devices().get_mut(&device)
+            && pointer.captured_by == Some(by)
+        {
+            pointer.captured_by = None;
+        }
+    }
+
+    /// Whether a pointer device is captured by `by`.
+    pub fn is_captured_by(&self, device: DeviceId, by: CaptureId) -> bool {
+        self.get(device).captured_by == Some(by)
+    }
+
+    /// Whether a pointer device is captured by someone other than `by`.
+    /// Interactors should ignore such pointers.
+    pub fn is_captured_by_other(
+        &self,
+        device: DeviceId,
+        by: CaptureId,
+    ) -> bool {
+        self.get(device)
+            .captured_by
+            .is_some_and(|captor| captor != by)
+    }
+
+    /// Updates the state of a pointer device with an event it emitted.
+    pub fn track(&self, device: DeviceId, event: &CursorEvent) {
+        let mut devices = self.devices();
+        let pointer = devices.entry(device).or_default();
+
+        match event {
+            CursorEvent::Moved { position } => {
+                pointer.position = Some(*position);
+            }
+            CursorEvent::Exited => {
+                pointer.position = None;
+            }
+            CursorEvent::Button(_, ButtonState::Pressed) => {
+                pointer.pressed_buttons += 1;
+            }
+            CursorEvent::Button(_, ButtonState::Released) => {
+                pointer.pressed_buttons =
+                    pointer.pressed_buttons.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+
+    /// Releases a pointer device's capture if none of its buttons are pressed anymore,
+    /// returning whether it did.
+    fn release_if_idle(&self, device: DeviceId) -> bool {
+        match self.devices().get_mut(&device) {
+            Some(pointer)
+                if pointer.pressed_buttons == 0
+                    && pointer.captured_by.is_some() =>
+            {
+                pointer.captured_by = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn devices(&self) -> MutexGuard<'_, HashMap<DeviceId, PointerState>> {
+        self.devices
+            .lock()
+            .expect("The pointers' lock was poisoned.")
+    }
+}
+
+/// A blueprint whose element keeps a [`Pointers`] context up to date
+/// with the cursor events shown to its content.
+pub struct PointerScopeBlueprint<A> {
+    pointers: Pointers,
+    blueprint: A,
+}
+
+impl<A> PointerScopeBlueprint<A> {
+    pub fn new(pointers: Pointers, blueprint: A) -> Self {
+        Self {
+            pointers,
+            blueprint,
+        }
+    }
+}
+
+impl<Env, A> Blueprint<Env> for PointerScopeBlueprint<A>
+where
+    Env: Environment,
+    A: Blueprint<Env>,
+{
+    type Element = PointerScopeElement<A::Element>;
+
+    fn make(self, env: &Env::BlueprintResources<'_>) -> Self::Element {
+        PointerScopeElement {
+            pointers: self.pointers,
+            element: self.blueprint.make(env),
+        }
+    }
+
+    fn reconcile(
+        self,
+        element: Pin<&mut Self::Element>,
+        env: &Env::BlueprintResources<'_>,
+    ) {
+        let element = element.project();
+        *element.pointers = self.pointers;
+        self.blueprint.reconcile(element.element, env);
+    }
+}
+
+/// The element made by a [`PointerScopeBlueprint`].
+#[pin_project]
+pub struct PointerScopeElement<E> {
+    pointers: Pointers,
+    #[pin]
+    element: E,
+}
+
+impl<E> Bubble<Event, EventResponse> for PointerScopeElement<E>
+where
+    E: Bubble<Event, EventResponse>,
+{
+    fn bubble(&mut self, event: &mut Event) -> EventResponse {
+        let Event::Cursor { id, event: cursor } = event else {
+            return self.element.bubble(event);
+        };
+        let device = *id;
+
+        // Content sees the pointer where it is now.
+        self.pointers.track(device, cursor);
+        let response = self.element.bubble(event);
+
+        // Releasing every button ends a capture even if its owner forgot about it
+        // (e.g. it was removed from the app in the middle of a drag).
+        if self.pointers.release_if_idle(device)
+            && response.pointer_capture.is_none()
+        {
+            return response
+                .with_pointer_capture(PointerCapture::Release(device));
+        }
+
+        response
+    }
+}
+
+impl<Env, E> Element<Env> for PointerScopeElement<E>
+where
+    Env: Environment,
+    E: Element<Env>,
+{
+    type Effect<'fx>
+        = E::Effect<'fx>
+    where
+        Self: 'fx;
+
+    fn effect(&self) -> Self::Effect<'_> {
+        self.element.effect()
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        env: &Env::BlueprintResources<'_>,
+    ) -> Poll<Option<()>> {
+        self.project().element.poll(cx, env)
+    }
+}
@@ -9,13 +9,35 @@
 //! In this crate, the description of how reactive UI reacts to signal changes is done with
 //! code from [`crate::state`]. But, like `Future`s need executors, this crate offers "App executors",
 //! which can poll the app's futures and signals.
+//!
+//! ## Frames
+//!
+//! A burst of changes (say, a signal updated many times in a row) shouldn't make the app
+//! present many times. An [`AsyncExecutor`] with a [target frame rate](AsyncExecutor::with_frame_rate)
+//! waits for the next frame before polling the app again, so that all the changes made in
+//! the meantime are presented together.
+//!
+//! Every frame advances a [`FrameClock`], which elements can listen to (for animations, say).
+//!
+//! ## Spawning
+//!
+//! Futures [spawned](Spawner::spawn) on an executor's [`Spawner`] are polled along with the app,
+//! right before it, so that whatever they change is presented in the same frame.
+//! That's how [effects](ui_composer_state::effect::Effect) can start work that takes a while,
+//! see [`Resource`](crate::app::composition::effects::resource::Resource).
 
 use crate::app::composition::elements::{Element, Environment};
-use futures_signals::signal::Signal;
+use futures::StreamExt as _;
+use futures::stream::FuturesUnordered;
+use futures_signals::signal::{Mutable, MutableSignal, Signal, SignalExt as _};
 use pin_project::pin_project;
+use std::future::Future;
 use std::ops::DerefMut;
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+use ui_composer_state::effect::animation::{clock::Clock, futures_time};
 
 type Own<A> = std::sync::Arc<std::sync::Mutex<A>>;
 
@@ -26,6 +48,9 @@ pub struct AsyncExecutor<'exec, Env: Environment, App: Element<Env>, Callback> {
     element: Own<App>,
     blueprint_resources: Env::BlueprintResources<'exec>,
     first_tick: bool,
+    scheduler: FrameScheduler,
+    spawner: Spawner,
+    tasks: FuturesUnordered<Task>,
     callback: Callback,
 }
 
@@ -41,9 +66,44 @@ impl<'exec, Env: Environment, App: Element<Env>, Callback>
             element,
             blueprint_resources: environment,
             first_tick: true,
+            scheduler: FrameScheduler::new(),
+            spawner: Spawner::global().clone(),
+            tasks: FuturesUnordered::new(),
             callback,
         }
     }
+
+    /// Polls the app at most `frames_per_second` times a second,
+    /// coalescing all the wakes in between into a single frame.
+    pub fn with_frame_rate(self, frames_per_second: f32) -> Self {
+        Self {
+            scheduler: self.scheduler.with_frame_rate(frames_per_second),
+            ..self
+        }
+    }
+
+    /// Advances `clock` every frame, instead of the [global one](FrameClock::global).
+    pub fn with_frame_clock(self, clock: FrameClock) -> Self {
+        Self {
+            scheduler: self.scheduler.with_frame_clock(clock),
+            ..self
+        }
+    }
+
+    /// The clock advanced every time this executor presents a frame.
+    pub fn frame_clock(&self) -> &FrameClock {
+        &self.scheduler.clock
+    }
+
+    /// Runs the futures spawned on `spawner`, instead of the [global one](Spawner::global).
+    pub fn with_spawner(self, spawner: Spawner) -> Self {
+        Self { spawner, ..self }
+    }
+
+    /// The spawner whose futures this executor runs.
+    pub fn spawner(&self) -> &Spawner {
+        &self.spawner
+    }
 }
 
 impl<'exec, Env: Environment, App: Element<Env>, Callback: FnMut()> Signal
@@ -59,25 +119,45 @@ impl<'exec, Env: Environment, App: Element<Env>, Callback: FnMut()> Signal
             element,
             blueprint_resources,
             first_tick,
+            scheduler,
+            spawner,
+            tasks,
             callback,
         } = self.project();
 
+        // Whatever woke us up before the next frame will be seen then.
+        if scheduler.poll_frame(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        tasks.extend(spawner.take_spawned(cx));
+        while let Poll::Ready(Some(())) = tasks.poll_next_unpin(cx) {}
+
         if let Ok(mut element_borrow) = element.lock() {
-            
             let pinned_element =
-            unsafe { Pin::new_unchecked(element_borrow.deref_mut()) };
-            
+                unsafe { Pin::new_unchecked(element_borrow.deref_mut()) };
+
             // Because of how signals work internally, we must yield at least once.
-            
+
             let inner_poll = pinned_element.poll(cx, blueprint_resources);
             if let Poll::Ready(None) = inner_poll
-            && *first_tick
+                && *first_tick
             {
                 *first_tick = false;
                 return Poll::Ready(Some(()));
             }
             *first_tick = false;
-            (callback)();
+            if let Poll::Ready(Some(())) = inner_poll {
+                scheduler.frame_presented();
+                (callback)();
+            }
+
+            // The app might be done, but what it spawned isn't.
+            if let Poll::Ready(None) = inner_poll
+                && !tasks.is_empty()
+            {
+                return Poll::Pending;
+            }
             inner_poll
         } else {
             cx.waker().wake_by_ref();
@@ -85,3 +165,276 @@ impl<'exec, Env: Environment, App: Element<Env>, Callback: FnMut()> Signal
         }
     }
 }
+
+type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A queue of futures for [`AsyncExecutor`]s to run along with their app.
+///
+/// Cloning a spawner gives another handle to the same queue.
+#[derive(Clone, Default)]
+pub struct Spawner {
+    shared: Arc<Mutex<SpawnerState>>,
+}
+
+#[derive(Default)]
+struct SpawnerState {
+    spawned: Vec<Task>,
+    /// The executors that took from the queue, to be woken up when there's more.
+    wakers: Vec<Waker>,
+}
+
+impl Spawner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The spawner shared by all executors that weren't given one.
+    pub fn global() -> &'static Spawner {
+        static GLOBAL: OnceLock<Spawner> = OnceLock::new();
+        GLOBAL.get_or_init(Spawner::new)
+    }
+
+    /// Queues `future` to be run by the next executor that polls its app.
+    ///
+    /// Every executor sharing this spawner is woken up, whichever takes it runs it.
+    pub fn spawn(&self, future: impl Future<Output = ()> + Send + 'static) {
+        let wakers = {
+            let mut state = self.shared.lock().unwrap();
+            state.spawned.push(Box::pin(future));
+            std::mem::take(&mut state.wakers)
+        };
+
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    /// Takes everything spawned so far, waking `cx` up when more is.
+    fn take_spawned(&self, cx: &mut Context) -> Vec<Task> {
+        let mut state = self.shared.lock().unwrap();
+        if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        std::mem::take(&mut state.spawned)
+    }
+}
+
+/// Decides when an [`AsyncExecutor`] may poll its app again.
+///
+/// After a frame is presented, the app isn't polled until the frame's time is up.
+/// Without a frame rate, the app is polled every time it's woken up.
+pub struct FrameScheduler {
+    frame_duration: Option<Duration>,
+    next_frame: Option<Instant>,
+    timer: Option<FrameTimer>,
+    clock: FrameClock,
+}
+
+impl FrameScheduler {
+    pub fn new() -> Self {
+        Self {
+            frame_duration: None,
+            next_frame: None,
+            timer: None,
+            clock: FrameClock::global().clone(),
+        }
+    }
+
+    pub fn with_frame_rate(self, frames_per_second: f32) -> Self {
+        Self {
+            frame_duration: (frames_per_second > 0.0)
+                .then(|| Duration::from_secs_f32(frames_per_second.recip())),
+            ..self
+        }
+    }
+
+    pub fn with_frame_clock(self, clock: FrameClock) -> Self {
+        Self { clock, ..self }
+    }
+
+    /// Whether a new frame can begin now.
+    ///
+    /// If it can't, `cx` is woken up when it can.
+    ///
+    /// This doesn't advance the clock, as polls that present nothing aren't frames.
+    /// Otherwise, elements listening to the clock would be woken up by every poll,
+    /// and poll again, forever.
+    pub fn poll_frame(&mut self, cx: &mut Context) -> Poll<()> {
+        let now = Instant::now();
+
+        if let Some(next_frame) = self.next_frame
+            && now < next_frame
+        {
+            self.timer
+                .get_or_insert_with(FrameTimer::new)
+                .wake_at(next_frame, cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        Poll::Ready(())
+    }
+
+    /// Tells the scheduler that a frame was just presented,
+    /// advancing the clock and making the next frame wait for its turn.
+    pub fn frame_presented(&mut self) {
+        let now = Instant::now();
+        self.clock.advance(now);
+        self.next_frame = self.frame_duration.map(|duration| now + duration);
+    }
+}
+
+impl Default for FrameScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Information about a frame, see [`FrameClock`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameTick {
+    /// How many frames came before this one.
+    pub index: u64,
+    /// When the frame began.
+    pub time: Instant,
+    /// How long since the previous frame began.
+    pub delta: Duration,
+}
+
+/// A clock that advances once per frame.
+///
+/// ```rust,ignore
+/// FrameClock::global()
+///     .signal()
+///     .map(|tick| Graphic::new(rect, color_at(tick.time)))
+///     .into_blueprint()
+/// ```
+///
+/// Listening to the clock keeps the app busy, presenting frame after frame,
+/// so only listen while there's something to animate.
+#[derive(Debug, Clone)]
+pub struct FrameClock {
+    tick: Mutable<FrameTick>,
+}
+
+impl FrameClock {
+    pub fn new() -> Self {
+        Self {
+            tick: Mutable::new(FrameTick {
+                index: 0,
+                time: Instant::now(),
+                delta: Duration::ZERO,
+            }),
+        }
+    }
+
+    /// The clock shared by all executors that weren't given one.
+    pub fn global() -> &'static FrameClock {
+        static GLOBAL: OnceLock<FrameClock> = OnceLock::new();
+        GLOBAL.get_or_init(FrameClock::new)
+    }
+
+    /// The current frame.
+    pub fn current(&self) -> FrameTick {
+        self.tick.get()
+    }
+
+    /// A signal of the current frame.
+    pub fn signal(&self) -> MutableSignal<FrameTick> {
+        self.tick.signal()
+    }
+
+    fn advance(&self, time: Instant) {
+        let mut tick = self.tick.lock_mut();
+        *tick = FrameTick {
+            index: tick.index + 1,
+            time,
+            delta: time.saturating_duration_since(tick.time),
+        };
+    }
+}
+
+impl Default for FrameClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Animations paced by a frame clock are synced to the app's frames.
+impl Clock for FrameClock {
+    fn now(&self) -> futures_time::time::Instant {
+        self.current().time.into()
+    }
+
+    fn next_frame(&self) -> impl Future<Output = ()> {
+        let index = self.current().index;
+        let next_frame = self
+            .tick
+            .signal_ref(move |tick| tick.index > index)
+            .wait_for(true);
+
+        async move {
+            next_frame.await;
+        }
+    }
+}
+
+/// Wakes a task up at some point in the future, from a background thread.
+struct FrameTimer {
+    shared: Arc<(Mutex<TimerState>, Condvar)>,
+}
+
+#[derive(Default)]
+struct TimerState {
+    alarm: Option<(Instant, Waker)>,
+    shut_down: bool,
+}
+
+impl FrameTimer {
+    fn new() -> Self {
+        let shared =
+            Arc::new((Mutex::new(TimerState::default()), Condvar::new()));
+
+        let thread_shared = shared.clone();
+        std::thread::spawn(move || {
+            let (state, alarm_set) = &*thread_shared;
+            let mut state = state.lock().unwrap();
+
+            while !state.shut_down {
+                match &state.alarm {
+                    None => state = alarm_set.wait(state).unwrap(),
+                    Some((deadline, _)) => {
+                        let now = Instant::now();
+                        if now >= *deadline {
+                            if let Some((_, waker)) = state.alarm.take() {
+                                waker.wake();
+                            }
+                        } else {
+                            let timeout = *deadline - now;
+                            state = alarm_set
+                                .wait_timeout(state, timeout)
+                                .unwrap()
+                                .0;
+                        }
+                    }
+                }
+            }
+        });
+
+        Self { shared }
+    }
+
+    /// Replaces the current alarm, if any.
+    fn wake_at(&self, deadline: Instant, waker: Waker) {
+        let (state, alarm_set) = &*self.shared;
+        state.lock().unwrap().alarm = Some((deadline, waker));
+        alarm_set.notify_one();
+    }
+}
+
+impl Drop for FrameTimer {
+    fn drop(&mut self) {
+        let (state, alarm_set) = &*self.shared;
+        if let Ok(mut state) = state.lock() {
+            state.shut_down = true;
+        }
+        alarm_set.notify_one();
+    }
+}
@@ -0,0 +1,253 @@
+//! # Headless
+//!
+//! A [`Runner`] that has no window, no terminal and no GPU attached.
+//!
+//! It is meant for testing: you give it a [`Blueprint`], feed it scripted [`Event`]s
+//! and inspect every effect the application produced along the way.
+//!
+//! ```rust,ignore
+//! let mut runner = HeadlessRunner::new(my_app_blueprint);
+//! runner.send(Event::Cursor { id: DeviceId(0), event: CursorEvent::Moved { position } });
+//!
+//! let frame = runner.last_frame().unwrap();
+//! assert!(frame.iter().any(|fx| fx.is::<RenderQuad>()));
+//! ```
+
+use crate::app::composition::algebra::Bubble;
+use crate::app::composition::elements::response::EventResponse;
+use crate::app::composition::elements::{Blueprint, Element, Environment};
+use crate::app::composition::visit::{Apply, DriveThru};
+use crate::app::runner::Runner;
+use crate::app::runner::futures::{AsyncExecutor, FrameClock, Spawner};
+use core::any::{Any, type_name};
+use core::fmt::Debug;
+use core::ops::Deref;
+use futures_signals::signal::Signal;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use ui_composer_input::event::Event;
+
+/// How many times the runner is willing to poll the application in a row
+/// before deciding it will never settle (e.g. an infinite animation).
+const MAX_TICKS_PER_SETTLE: usize = 1024;
+
+/// An environment for applications running without any platform attached.
+pub struct HeadlessEnvironment;
+
+impl Environment for HeadlessEnvironment {
+    type BlueprintResources<'make> = HeadlessBlueprintResources;
+    type EffectVisitor<'fx> = HeadlessEffectVisitor<'fx>;
+}
+
+/// Resources available when making blueprints in a [`HeadlessEnvironment`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeadlessBlueprintResources;
+
+/// A visitor that records every effect driven through it
+/// instead of performing it.
+pub struct HeadlessEffectVisitor<'fx> {
+    pub effects: &'fx mut Frame,
+}
+
+impl<'fx, T> Apply<T> for HeadlessEffectVisitor<'fx>
+where
+    T: Any + Clone + Send,
+{
+    fn visit(&mut self, effect: &T) {
+        self.effects.push(RecordedEffect::new(effect.clone()));
+    }
+}
+
+/// All the effects an application produced at a point in time,
+/// in the order they were visited.
+pub type Frame = Vec<RecordedEffect>;
+
+/// An effect captured by a [`HeadlessEffectVisitor`].
+pub struct RecordedEffect {
+    type_name: &'static str,
+    effect: Box<dyn Any + Send>,
+}
+
+impl RecordedEffect {
+    pub fn new<T: Any + Send>(effect: T) -> Self {
+        Self {
+            type_name: type_name::<T>(),
+            effect: Box::new(effect),
+        }
+    }
+
+    /// The name of the effect's type, useful for debugging.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Whether this effect is of type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.effect.is::<T>()
+    }
+
+    /// Returns the effect if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.effect.downcast_ref::<T>()
+    }
+}
+
+impl Debug for RecordedEffect {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("RecordedEffect")
+            .field(&self.type_name)
+            .finish()
+    }
+}
+
+type HeadlessExecutor<App> =
+    AsyncExecutor<'static, HeadlessEnvironment, App, fn()>;
+
+/// A runner that drives an application without any platform,
+/// recording each frame of effects it produces.
+///
+/// Each time the application reports a change, a new [`Frame`] is recorded.
+pub struct HeadlessRunner<AppBlueprint>
+where
+    AppBlueprint: Blueprint<HeadlessEnvironment>,
+{
+    element: Arc<Mutex<AppBlueprint::Element>>,
+    executor: Pin<Box<HeadlessExecutor<AppBlueprint::Element>>>,
+    frames: Vec<Frame>,
+}
+
+impl<AppBlueprint> HeadlessRunner<AppBlueprint>
+where
+    AppBlueprint: Blueprint<HeadlessEnvironment>,
+{
+    /// Makes the application and lets it settle.
+    pub fn new(blueprint: AppBlueprint) -> Self {
+        let element = blueprint.make(&HeadlessBlueprintResources);
+        let element = Arc::new(Mutex::new(element));
+        // Frames aren't paced, and neither the clock nor the spawner are shared
+        // with other runners, so that tests can be run side by side.
+        let executor = AsyncExecutor::new(
+            element.clone(),
+            HeadlessBlueprintResources,
+            (|| {}) as fn(),
+        )
+        .with_frame_clock(FrameClock::new())
+        .with_spawner(Spawner::new());
+
+        let mut runner = Self {
+            element,
+            executor: Box::pin(executor),
+            frames: Vec::new(),
+        };
+        runner.settle();
+        runner
+    }
+
+    /// Shows an event to the application, then lets it settle.
+    pub fn send(&mut self, mut event: Event) -> EventResponse {
+        let response = self.lock().bubble(&mut event);
+        self.settle();
+        response
+    }
+
+    /// Shows several events to the application, in order,
+    /// letting it settle after each one.
+    pub fn send_all(&mut self, events: impl IntoIterator<Item = Event>) {
+        for event in events {
+            let _ = self.send(event);
+        }
+    }
+
+    /// Polls the application until it has nothing left to do right now,
+    /// recording a frame every time it changes.
+    ///
+    /// Futures and signals waiting on the outside world (timers, IO, etc.)
+    /// are left pending, since nothing will wake them up.
+    pub fn settle(&mut self) {
+        let mut cx = Context::from_waker(Waker::noop());
+
+        for _ in 0..MAX_TICKS_PER_SETTLE {
+            match self.executor.as_mut().poll_change(&mut cx) {
+                Poll::Ready(Some(())) => self.record(),
+                Poll::Ready(None) | Poll::Pending => return,
+            }
+        }
+    }
+
+    fn record(&mut self) {
+        let mut frame = Frame::new();
+        self.lock().effect().drive_thru(&mut HeadlessEffectVisitor {
+            effects: &mut frame,
+        });
+        self.frames.push(frame);
+    }
+
+    /// Gives access to the running application.
+    ///
+    /// The application is pinned while it runs, so it is only lent immutably.
+    pub fn element(&self) -> ElementRef<'_, AppBlueprint::Element> {
+        ElementRef(self.lock())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, AppBlueprint::Element> {
+        self.element
+            .lock()
+            .expect("The application's lock was poisoned.")
+    }
+
+    /// The spawner whose futures this runner runs,
+    /// which [`AsyncEffect`](crate::app::composition::effects::resource::AsyncEffect)s
+    /// should be given with `with_spawner`.
+    pub fn spawner(&self) -> &Spawner {
+        self.executor.spawner()
+    }
+
+    /// Every frame recorded so far, oldest first.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// The most recently recorded frame.
+    pub fn last_frame(&self) -> Option<&Frame> {
+        self.frames.last()
+    }
+
+    /// The effects of type `T` in the most recently recorded frame, in order.
+    ///
+    /// Panics if no frame was recorded yet.
+    pub fn effects_of<T: Any + Clone>(&self) -> Vec<T> {
+        self.last_frame()
+            .expect("The app should have rendered at least once.")
+            .iter()
+            .filter_map(|fx| fx.downcast_ref::<T>().cloned())
+            .collect()
+    }
+
+    /// Takes every frame recorded so far, leaving none behind.
+    pub fn take_frames(&mut self) -> Vec<Frame> {
+        core::mem::take(&mut self.frames)
+    }
+}
+
+/// A running application lent by [`HeadlessRunner::element`].
+pub struct ElementRef<'a, E>(MutexGuard<'a, E>);
+
+impl<E> Deref for ElementRef<'_, E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.0
+    }
+}
+
+impl<AppBlueprint> Runner for HeadlessRunner<AppBlueprint>
+where
+    AppBlueprint: Blueprint<HeadlessEnvironment>,
+{
+    type AppBlueprint = AppBlueprint;
+
+    fn run(ui: Self::AppBlueprint) {
+        let _ = HeadlessRunner::new(ui);
+    }
+}
@@ -11,6 +11,7 @@
 //! all the way to the root, which is a [`Runner`]. Then, effects will be executed.
 
 pub mod futures;
+pub mod headless;
 
 /// An application runner. It bubbles down Affects and bubble up Effects,
 /// effectively allowing the pure, immutable application to perform IO.
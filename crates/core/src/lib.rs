@@ -2,15 +2,21 @@ pub mod app;
 
 pub mod prelude {
     pub use crate::app::composition::algebra::{
-        Bubble, Empty, Gather, Monoid, Semigroup,
+        Bubble, Empty, Gather, Monoid, Propagate, Semigroup,
     };
     pub use crate::app::composition::effects::{
-        future::IntoBlueprint as _, signal::IntoBlueprint as _,
+        future::IntoBlueprint as _, future::IntoTryBlueprint as _,
+        resource::Resource, signal::IntoBlueprint as _,
+        signal_map::IntoBlueprint as _, signal_vec::IntoBlueprint as _,
+        stream::Accumulate as _, stream::IntoBlueprint as _,
     };
     pub use crate::app::composition::elements::{
-        Blueprint, Element, Environment,
+        Blueprint, Element, Environment, TryBlueprint,
+        error_boundary::ErrorBoundary, response::EventResponse,
     };
+    pub use crate::app::composition::focus::{FocusScope, Focusable};
     pub use crate::app::composition::layout::{ItemBox, LayoutItem, Resizable};
+    pub use crate::app::composition::pointer::{CaptureId, Pointers};
     pub use crate::app::composition::visit::{
         Apply, ApplyMut, DriveThru, DriveThruMut,
     };
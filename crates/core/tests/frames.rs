@@ -0,0 +1,22 @@
+use std::task::{Context, Poll, Waker};
+use ui_composer_core::app::runner::futures::{FrameClock, FrameScheduler};
+
+#[test]
+fn frames_wait_for_their_turn() {
+    let clock = FrameClock::new();
+    let mut scheduler = FrameScheduler::new()
+        .with_frame_rate(1.0)
+        .with_frame_clock(clock.clone());
+    let mut cx = Context::from_waker(Waker::noop());
+
+    // Nothing was presented, so there's nothing to wait for,
+    // and polls that present nothing don't advance the clock.
+    assert_eq!(scheduler.poll_frame(&mut cx), Poll::Ready(()));
+    assert_eq!(scheduler.poll_frame(&mut cx), Poll::Ready(()));
+    assert_eq!(clock.current().index, 0);
+
+    scheduler.frame_presented();
+    assert_eq!(clock.current().index, 1);
+    assert_eq!(scheduler.poll_frame(&mut cx), Poll::Pending);
+    assert_eq!(clock.current().index, 1);
+}
@@ -0,0 +1,91 @@
+use futures::channel::oneshot;
+use futures_signals::signal::Signal as _;
+use std::pin::pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Wake, Waker};
+use ui_composer_core::app::composition::effects::resource::{Resource, Status};
+use ui_composer_core::app::runner::futures::{AsyncExecutor, Spawner};
+use ui_composer_core::app::runner::headless::{
+    HeadlessBlueprintResources, HeadlessEnvironment,
+};
+use ui_composer_state::effect::Effect as _;
+
+#[test]
+fn async_effects_report_their_status() {
+    let spawner = Spawner::new();
+    let mut executor = pin!(
+        AsyncExecutor::<HeadlessEnvironment, (), _>::new(
+            Arc::new(Mutex::new(())),
+            HeadlessBlueprintResources,
+            || {},
+        )
+        .with_spawner(spawner.clone())
+    );
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let (respond, response) = oneshot::channel::<u32>();
+    let response = Arc::new(Mutex::new(Some(response)));
+    let resource = Resource::<u32, String>::new();
+    let mut fetch = resource
+        .effect(move || {
+            let response = response.lock().unwrap().take();
+            async move {
+                match response {
+                    Some(response) => response.await.map_err(|e| e.to_string()),
+                    None => Err("Fetched twice.".to_string()),
+                }
+            }
+        })
+        .with_spawner(spawner);
+    assert_eq!(resource.get(), Status::Idle);
+
+    fetch.apply();
+    assert_eq!(resource.get(), Status::Loading);
+    let _ = executor.as_mut().poll_change(&mut cx);
+    assert_eq!(resource.get(), Status::Loading);
+
+    respond.send(7).unwrap();
+    let _ = executor.as_mut().poll_change(&mut cx);
+    assert_eq!(resource.get(), Status::Success(7));
+}
+
+#[test]
+fn spawning_wakes_every_executor_sharing_the_spawner() {
+    struct Count(AtomicUsize);
+    impl Wake for Count {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let spawner = Spawner::new();
+    let executor = || {
+        Box::pin(
+            AsyncExecutor::<HeadlessEnvironment, (), _>::new(
+                Arc::new(Mutex::new(())),
+                HeadlessBlueprintResources,
+                || {},
+            )
+            .with_spawner(spawner.clone()),
+        )
+    };
+    let wakes = [
+        Arc::new(Count(AtomicUsize::new(0))),
+        Arc::new(Count(AtomicUsize::new(0))),
+    ];
+
+    for (mut executor, wakes) in
+        [executor(), executor()].into_iter().zip(&wakes)
+    {
+        let waker = Waker::from(wakes.clone());
+        let _ = executor
+            .as_mut()
+            .poll_change(&mut Context::from_waker(&waker));
+    }
+    spawner.spawn(async {});
+
+    for wakes in &wakes {
+        assert_eq!(wakes.0.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,597 @@
+//! Derives for the traits every primitive implements: `Blueprint`, `Element`, `Bubble` and `DriveThru`.
+//!
+//! All of them delegate field-wise, in declaration order, the same way tuples do,
+//! except for "leaf" types (given `#[element(effect = ...)]`) which have no children.
+
+use {
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    proc_macro_error2::abort,
+    quote::{format_ident, quote},
+    syn::{
+        parse_quote, punctuated::Punctuated, Attribute, Data, DeriveInput,
+        Field, Fields, GenericParam, Generics, Ident, Index, LitStr, Member,
+        Path, Token, Type, WherePredicate,
+    },
+};
+
+/// What was given in `#[element(...)]` on the type.
+#[derive(Default)]
+struct ElementOptions {
+    /// The environments to implement the traits for,
+    /// or every environment if none were given.
+    envs: Vec<Path>,
+    /// The effect of a leaf element, made with `From<&Self>`.
+    effect: Option<Type>,
+    /// A `fn(Self, Pin<&mut Self>)` to use as `Blueprint::reconcile`.
+    reconcile: Option<Path>,
+    /// Extra bounds for the generated impls.
+    bounds: Vec<WherePredicate>,
+}
+
+fn element_options(input: &DeriveInput) -> ElementOptions {
+    let mut options = ElementOptions::default();
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("element")) {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("env") {
+                options.envs.push(meta.value()?.parse()?);
+            } else if meta.path.is_ident("effect") {
+                options.effect = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("reconcile") {
+                options.reconcile = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("bound") {
+                let bounds: LitStr = meta.value()?.parse()?;
+                options.bounds.extend(bounds.parse_with(
+                    Punctuated::<WherePredicate, Token![,]>::parse_terminated,
+                )?);
+            } else {
+                return Err(meta.error(
+                    "expected `env`, `effect`, `reconcile` or `bound`",
+                ));
+            }
+            Ok(())
+        });
+
+        if let Err(error) = result {
+            abort!(error.span(), "{}", error);
+        }
+    }
+
+    options
+}
+
+/// Whether a field has `#[<attr_name>(skip)]`.
+fn is_skipped(field: &Field, attr_name: &str) -> bool {
+    has_flag(&field.attrs, attr_name, "skip")
+}
+
+fn has_flag(attrs: &[Attribute], attr_name: &str, flag: &str) -> bool {
+    let mut found = false;
+    for attr in attrs.iter().filter(|a| a.path().is_ident(attr_name)) {
+        // Unknown options are reported by whoever reads them.
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(flag) {
+                found = true;
+            }
+            Ok(())
+        });
+    }
+    found
+}
+
+/// A pattern that binds every field of a struct or variant that isn't skipped,
+/// along with those bindings and the types of the fields bound.
+fn destructure(
+    path: TokenStream2,
+    fields: &Fields,
+    attr_name: &str,
+) -> (TokenStream2, Vec<Ident>, Vec<Type>) {
+    let mut bindings = Vec::new();
+    let mut types = Vec::new();
+
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let bound = named
+                .named
+                .iter()
+                .filter(|field| !is_skipped(field, attr_name))
+                .map(|field| {
+                    let name = &field.ident;
+                    let binding = format_ident!("__field_{}", bindings.len());
+                    let pattern = quote! { #name: #binding };
+                    bindings.push(binding);
+                    types.push(field.ty.clone());
+                    pattern
+                })
+                .collect::<Vec<_>>();
+            quote! { #path { #(#bound,)* .. } }
+        }
+        Fields::Unnamed(unnamed) => {
+            let elements = unnamed
+                .unnamed
+                .iter()
+                .map(|field| {
+                    if is_skipped(field, attr_name) {
+                        return quote! { _ };
+                    }
+                    let binding = format_ident!("__field_{}", bindings.len());
+                    let pattern = quote! { #binding };
+                    bindings.push(binding);
+                    types.push(field.ty.clone());
+                    pattern
+                })
+                .collect::<Vec<_>>();
+            quote! { #path(#(#elements),*) }
+        }
+        Fields::Unit => quote! { #path },
+    };
+
+    (pattern, bindings, types)
+}
+
+/// Match arms (over `self`) for every struct or variant of the type.
+///
+/// `arm` builds the body of each arm from the bindings of the fields.
+fn match_arms<F>(
+    input: &DeriveInput,
+    attr_name: &str,
+    mut arm: F,
+) -> (TokenStream2, Vec<Type>)
+where
+    F: FnMut(&[Ident]) -> TokenStream2,
+{
+    let mut all_types = Vec::new();
+
+    let arms = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, bindings, types) =
+                destructure(quote! { Self }, &data.fields, attr_name);
+            let body = arm(&bindings);
+            all_types.extend(types);
+            quote! { #pattern => { #body } }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let name = &variant.ident;
+                let (pattern, bindings, types) = destructure(
+                    quote! { Self::#name },
+                    &variant.fields,
+                    attr_name,
+                );
+                let body = arm(&bindings);
+                all_types.extend(types);
+                quote! { #pattern => { #body } }
+            });
+            quote! { #(#arms)* }
+        }
+        Data::Union(_) => {
+            abort!(Span::call_site(), "unions can't be derived through")
+        }
+    };
+
+    (arms, all_types)
+}
+
+fn with_param(generics: &Generics, param: GenericParam) -> Generics {
+    let mut generics = generics.clone();
+    generics.params.push(param);
+    generics
+}
+
+fn with_predicates<I>(generics: &Generics, predicates: I) -> Generics
+where
+    I: IntoIterator<Item = WherePredicate>,
+{
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// The environments to implement for, along with the generics to use for each.
+fn environments(
+    input: &DeriveInput,
+    options: &ElementOptions,
+) -> Vec<(TokenStream2, Generics)> {
+    let generics = with_predicates(&input.generics, options.bounds.clone());
+
+    if options.envs.is_empty() {
+        let generics = with_param(
+            &generics,
+            parse_quote! {
+                __Env: ::ui_composer_core::app::composition::elements::Environment
+            },
+        );
+        vec![(quote! { __Env }, generics)]
+    } else {
+        options
+            .envs
+            .iter()
+            .map(|env| (quote! { #env }, generics.clone()))
+            .collect()
+    }
+}
+
+pub fn derive_bubble(input: &DeriveInput) -> TokenStream2 {
+    let name = &input.ident;
+    let options = element_options(input);
+
+    let algebra = quote! { ::ui_composer_core::app::composition::algebra };
+    let event = quote! { ::ui_composer_input::event::Event };
+    let response = quote! { ::ui_composer_core::app::composition::elements::response::EventResponse };
+
+    // Leaves have no children to bubble events to.
+    let (body, types) = if options.effect.is_some() {
+        (quote! { #algebra::Empty::empty() }, Vec::new())
+    } else {
+        let (arms, types) = match_arms(input, "element", |bindings| {
+            quote! {
+                let __response: #response = #algebra::Empty::empty();
+                #(
+                    let __response = #algebra::Semigroup::combine(
+                        __response,
+                        #algebra::Bubble::bubble(#bindings, event),
+                    );
+                    if #algebra::Propagate::stops_propagation(&__response) {
+                        return __response;
+                    }
+                )*
+                __response
+            }
+        });
+        (quote! { match self { #arms } }, types)
+    };
+
+    let generics = with_predicates(
+        &with_predicates(&input.generics, options.bounds.clone()),
+        types.iter().map(
+            |ty| parse_quote! { #ty: #algebra::Bubble<#event, #response> },
+        ),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #algebra::Bubble<#event, #response> for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn bubble(&mut self, event: &mut #event) -> #response {
+                #body
+            }
+        }
+    }
+}
+
+pub fn derive_element(input: &DeriveInput) -> TokenStream2 {
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let options = element_options(input);
+
+    let algebra = quote! { ::ui_composer_core::app::composition::algebra };
+    let elements = quote! { ::ui_composer_core::app::composition::elements };
+    let visit = quote! { ::ui_composer_core::app::composition::visit };
+
+    let impls = environments(input, &options).into_iter().map(|(env, generics)| {
+        let resources = quote! { <#env as #elements::Environment>::BlueprintResources<'_> };
+
+        let (generics, items) = match &options.effect {
+            Some(effect) => {
+                let effect_fn = match effect {
+                    Type::Tuple(unit) if unit.elems.is_empty() => quote! {
+                        fn effect(&self) -> Self::Effect<'_> {}
+                    },
+                    _ => quote! {
+                        fn effect(&self) -> Self::Effect<'_> {
+                            <#effect as ::core::convert::From<&Self>>::from(self)
+                        }
+                    },
+                };
+                let generics = with_predicates(&generics, [parse_quote! {
+                    for<'__fx> #effect: #visit::DriveThru<<#env as #elements::Environment>::EffectVisitor<'__fx>>
+                }]);
+                let items = quote! {
+                    type Effect<'fx> = #effect where Self: 'fx;
+
+                    #effect_fn
+                };
+                (generics, items)
+            }
+            None if matches!(input.data, Data::Enum(_)) => enum_element(input, &env, &resources, generics),
+            None => {
+                let Data::Struct(data) = &input.data else {
+                    abort!(Span::call_site(), "unions can't be derived through")
+                };
+                let fields = data
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, field)| !is_skipped(field, "element"))
+                    .map(|(index, field)| {
+                        let member = match &field.ident {
+                            Some(ident) => Member::Named(ident.clone()),
+                            None => Member::Unnamed(Index::from(index)),
+                        };
+                        (member, field.ty.clone())
+                    })
+                    .collect::<Vec<_>>();
+                let members = fields.iter().map(|(member, _)| member).collect::<Vec<_>>();
+                let types = fields.iter().map(|(_, ty)| ty).collect::<Vec<_>>();
+
+                let effect_type = cons_list(
+                    types.iter().map(|ty| quote! { <#ty as #elements::Element<#env>>::Effect<'fx> }),
+                );
+                let effect_expr = cons_list(
+                    fields.iter().map(|(member, ty)| quote! {
+                        <#ty as #elements::Element<#env>>::effect(&self.#member)
+                    }),
+                );
+
+                let generics = with_predicates(
+                    &generics,
+                    types.iter().map(|ty| parse_quote! { #ty: #elements::Element<#env> }),
+                );
+                let items = quote! {
+                    type Effect<'fx> = #effect_type where Self: 'fx;
+
+                    fn effect(&self) -> Self::Effect<'_> {
+                        #effect_expr
+                    }
+
+                    fn poll(
+                        self: ::core::pin::Pin<&mut Self>,
+                        cx: &mut ::core::task::Context,
+                        env: &#resources,
+                    ) -> ::core::task::Poll<::core::option::Option<()>> {
+                        // SAFETY: The fields are pinned structurally, and are never moved out of `self`.
+                        let this = unsafe { self.get_unchecked_mut() };
+                        let __poll = #algebra::Empty::empty();
+                        #(
+                            let __poll = #algebra::Semigroup::combine(
+                                __poll,
+                                <#types as #elements::Element<#env>>::poll(
+                                    unsafe { ::core::pin::Pin::new_unchecked(&mut this.#members) },
+                                    cx,
+                                    env,
+                                ),
+                            );
+                        )*
+                        __poll
+                    }
+                };
+                (generics, items)
+            }
+        };
+
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        quote! {
+            impl #impl_generics #elements::Element<#env> for #name #ty_generics #where_clause {
+                #items
+            }
+        }
+    }).collect::<Vec<_>>();
+
+    // Leaves don't pin their fields.
+    let guards = options.effect.is_none().then(|| pin_guards(input));
+
+    quote! {
+        #(#impls)*
+        #guards
+    }
+}
+
+/// Makes pinning the fields of a type in `Element::poll` sound,
+/// the same way `pin_project` does.
+///
+/// The type is only [`Unpin`] if the fields it pins are, which also keeps
+/// anyone from implementing it by hand, and it can't implement [`Drop`],
+/// which could move the fields out of a pinned `self`.
+fn pin_guards(input: &DeriveInput) -> TokenStream2 {
+    let name = &input.ident;
+    let (_, types) = match_arms(input, "element", |_| quote! {});
+
+    let unpin_generics = with_predicates(
+        &input.generics,
+        types
+            .iter()
+            .map(|ty| parse_quote! { #ty: ::core::marker::Unpin }),
+    );
+    let (impl_generics, ty_generics, where_clause) =
+        unpin_generics.split_for_impl();
+    let (drop_impl_generics, _, drop_where_clause) =
+        input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::core::marker::Unpin for #name #ty_generics #where_clause {}
+
+        const _: () = {
+            trait MustNotImplDrop {}
+            #[allow(drop_bounds)]
+            impl<T: ::core::ops::Drop> MustNotImplDrop for T {}
+            impl #drop_impl_generics MustNotImplDrop for #name #ty_generics #drop_where_clause {}
+        };
+    }
+}
+
+/// The generics and items of an `Element` impl for an enum that isn't a leaf.
+///
+/// Its effect has an `Option` for each variant, in declaration order,
+/// of which only the one of the current variant is `Some`.
+fn enum_element(
+    input: &DeriveInput,
+    env: &TokenStream2,
+    resources: &TokenStream2,
+    generics: Generics,
+) -> (Generics, TokenStream2) {
+    let algebra = quote! { ::ui_composer_core::app::composition::algebra };
+    let elements = quote! { ::ui_composer_core::app::composition::elements };
+
+    let Data::Enum(data) = &input.data else {
+        unreachable!("only called for enums")
+    };
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let name = &variant.ident;
+            destructure(quote! { Self::#name }, &variant.fields, "element")
+        })
+        .collect::<Vec<_>>();
+
+    let effect_type = cons_list(variants.iter().map(|(_, _, types)| {
+        let effects = cons_list(types.iter().map(
+            |ty| quote! { <#ty as #elements::Element<#env>>::Effect<'fx> },
+        ));
+        quote! { ::core::option::Option<#effects> }
+    }));
+
+    let effect_arms = variants.iter().enumerate().map(|(index, (pattern, bindings, types))| {
+        let effects = cons_list(bindings.iter().zip(types).map(
+            |(binding, ty)| quote! { <#ty as #elements::Element<#env>>::effect(#binding) },
+        ));
+        let effect = cons_list((0..variants.len()).map(|other| {
+            if other == index {
+                quote! { ::core::option::Option::Some(#effects) }
+            } else {
+                quote! { ::core::option::Option::None }
+            }
+        }));
+        quote! { #pattern => #effect, }
+    });
+
+    let poll_arms = variants.iter().map(|(pattern, bindings, types)| {
+        quote! {
+            #pattern => {
+                let __poll = #algebra::Empty::empty();
+                #(
+                    let __poll = #algebra::Semigroup::combine(
+                        __poll,
+                        <#types as #elements::Element<#env>>::poll(
+                            unsafe { ::core::pin::Pin::new_unchecked(#bindings) },
+                            cx,
+                            env,
+                        ),
+                    );
+                )*
+                __poll
+            }
+        }
+    });
+
+    let generics = with_predicates(
+        &generics,
+        variants
+            .iter()
+            .flat_map(|(_, _, types)| types)
+            .map(|ty| parse_quote! { #ty: #elements::Element<#env> }),
+    );
+    let items = quote! {
+        type Effect<'fx> = #effect_type where Self: 'fx;
+
+        #[allow(unused_variables)]
+        fn effect(&self) -> Self::Effect<'_> {
+            match self {
+                #(#effect_arms)*
+            }
+        }
+
+        #[allow(unused_variables)]
+        fn poll(
+            self: ::core::pin::Pin<&mut Self>,
+            cx: &mut ::core::task::Context,
+            env: &#resources,
+        ) -> ::core::task::Poll<::core::option::Option<()>> {
+            // SAFETY: The fields are pinned structurally, and are never moved out of `self`.
+            match unsafe { self.get_unchecked_mut() } {
+                #(#poll_arms)*
+            }
+        }
+    };
+
+    (generics, items)
+}
+
+pub fn derive_blueprint(input: &DeriveInput) -> TokenStream2 {
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let options = element_options(input);
+
+    let elements = quote! { ::ui_composer_core::app::composition::elements };
+
+    let impls = environments(input, &options).into_iter().map(|(env, generics)| {
+        let resources = quote! { <#env as #elements::Environment>::BlueprintResources<'_> };
+        let generics = with_predicates(&generics, [parse_quote! { Self: #elements::Element<#env> }]);
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        let reconcile = options.reconcile.as_ref().map(|reconcile| {
+            quote! {
+                fn reconcile(self, element: ::core::pin::Pin<&mut Self::Element>, _: &#resources) {
+                    #reconcile(self, element)
+                }
+            }
+        });
+
+        quote! {
+            impl #impl_generics #elements::Blueprint<#env> for #name #ty_generics #where_clause {
+                type Element = Self;
+
+                fn make(self, _: &#resources) -> Self::Element {
+                    self
+                }
+
+                #reconcile
+            }
+        }
+    });
+
+    quote! { #(#impls)* }
+}
+
+pub fn derive_drive_thru(input: &DeriveInput) -> TokenStream2 {
+    let name = &input.ident;
+    let visit = quote! { ::ui_composer_core::app::composition::visit };
+
+    // Leaves are visited themselves, instead of being driven through.
+    let (body, types) = if has_flag(&input.attrs, "drive_thru", "visit") {
+        (quote! { #visit::Apply::visit(visitor, self) }, Vec::new())
+    } else {
+        let (arms, types) = match_arms(input, "drive_thru", |bindings| {
+            quote! { #( #visit::DriveThru::drive_thru(#bindings, visitor); )* }
+        });
+        (quote! { match self { #arms } }, types)
+    };
+
+    let mut generics = with_param(&input.generics, parse_quote! { __V });
+    if has_flag(&input.attrs, "drive_thru", "visit") {
+        generics = with_predicates(
+            &generics,
+            [parse_quote! { __V: #visit::Apply<Self> }],
+        );
+    }
+    let generics = with_predicates(
+        &generics,
+        types
+            .iter()
+            .map(|ty| parse_quote! { #ty: #visit::DriveThru<__V> }),
+    );
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #visit::DriveThru<__V> for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn drive_thru(&self, visitor: &mut __V) {
+                #body
+            }
+        }
+    }
+}
+
+/// Transform a list of types or expressions into a tuple-based cons list.
+fn cons_list<I>(mut iter: I) -> TokenStream2
+where
+    I: Iterator<Item = TokenStream2>,
+{
+    if let Some(first) = iter.next() {
+        let rest = cons_list(iter);
+        quote!((#first, #rest))
+    } else {
+        quote!(())
+    }
+}
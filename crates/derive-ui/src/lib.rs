@@ -6,6 +6,53 @@ use {
     syn::{parse_macro_input, Data, DataStruct, DeriveInput, Field, Path},
 };
 
+mod element;
+
+/// Implements `Blueprint` for an element that is its own blueprint.
+///
+/// Implemented for every environment, or for the ones given with `#[element(env = ...)]`.
+/// Elements are reconciled by making them anew, unless `#[element(reconcile = path)]`
+/// gives a `fn(Self, Pin<&mut Self>)` that patches the old element instead.
+#[proc_macro_derive(Blueprint, attributes(element))]
+#[proc_macro_error]
+pub fn derive_blueprint(input: TokenStream) -> TokenStream {
+    element::derive_blueprint(&parse_macro_input!(input as DeriveInput)).into()
+}
+
+/// Implements `Element` by delegating to each field, like tuples do:
+/// the effect is a cons list of the fields' effects, and polling polls every field.
+///
+/// Enums have an effect with an `Option` per variant, of which only the current one's is `Some`.
+///
+/// Fields with `#[element(skip)]` are left out.
+/// Leaf elements, with no children, instead give their effect type with
+/// `#[element(effect = RenderSomething)]`, which is made from `&Self` with `From`.
+#[proc_macro_derive(Element, attributes(element))]
+#[proc_macro_error]
+pub fn derive_element(input: TokenStream) -> TokenStream {
+    element::derive_element(&parse_macro_input!(input as DeriveInput)).into()
+}
+
+/// Implements `Bubble<Event, EventResponse>` by bubbling the event through each field,
+/// in order, until one of them stops propagation.
+///
+/// Fields with `#[element(skip)]` are left out, and leaf elements ignore all events.
+#[proc_macro_derive(Bubble, attributes(element))]
+#[proc_macro_error]
+pub fn derive_bubble(input: TokenStream) -> TokenStream {
+    element::derive_bubble(&parse_macro_input!(input as DeriveInput)).into()
+}
+
+/// Implements `DriveThru` by driving the visitor through each field, in order.
+///
+/// Fields with `#[drive_thru(skip)]` are left out. Effects that are visited
+/// themselves, instead of their fields, are marked with `#[drive_thru(visit)]`.
+#[proc_macro_derive(DriveThru, attributes(drive_thru))]
+#[proc_macro_error]
+pub fn derive_drive_thru(input: TokenStream) -> TokenStream {
+    element::derive_drive_thru(&parse_macro_input!(input as DeriveInput)).into()
+}
+
 #[proc_macro_derive(
     ToDefaultUi,
     attributes(container, to_ui, props, wrap, ui_exclude)
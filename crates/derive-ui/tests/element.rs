@@ -0,0 +1,131 @@
+use ui_composer_core::app::composition::{
+    algebra::Bubble as _,
+    elements::{response::EventResponse, Element},
+    visit::{Apply, DriveThru as _},
+};
+use ui_composer_core::app::runner::headless::HeadlessEnvironment;
+use ui_composer_derive_ui::{Bubble, DriveThru, Element};
+use ui_composer_input::event::Event;
+
+#[derive(Clone, DriveThru)]
+#[drive_thru(visit)]
+struct Draw(&'static str);
+
+#[derive(DriveThru)]
+struct Layer {
+    background: Draw,
+    #[drive_thru(skip)]
+    _name: String,
+    children: Vec<Draw>,
+}
+
+#[derive(DriveThru)]
+enum Effect {
+    Single(Draw),
+    Layer(Layer),
+    Nothing,
+}
+
+struct Log(Vec<&'static str>);
+
+impl Apply<Draw> for Log {
+    fn visit(&mut self, Draw(name): &Draw) {
+        self.0.push(*name);
+    }
+}
+
+#[test]
+fn effects_are_driven_through_field_by_field() {
+    let effects = vec![
+        Effect::Single(Draw("a")),
+        Effect::Nothing,
+        Effect::Layer(Layer {
+            background: Draw("b"),
+            _name: "layer".into(),
+            children: vec![Draw("c"), Draw("d")],
+        }),
+    ];
+
+    let mut log = Log(Vec::new());
+    effects.drive_thru(&mut log);
+    assert_eq!(log.0, ["a", "b", "c", "d"]);
+}
+
+struct Counter {
+    count: usize,
+    stops: bool,
+}
+
+impl ui_composer_core::app::composition::algebra::Bubble<Event, EventResponse>
+    for Counter
+{
+    fn bubble(&mut self, _: &mut Event) -> EventResponse {
+        self.count += 1;
+        if self.stops {
+            EventResponse::handled().with_stop_propagation()
+        } else {
+            EventResponse::ignored()
+        }
+    }
+}
+
+#[derive(Bubble)]
+struct Stack {
+    top: Counter,
+    #[element(skip)]
+    _label: String,
+    bottom: Counter,
+}
+
+#[test]
+fn events_bubble_field_by_field_until_stopped() {
+    let counter = |stops| Counter { count: 0, stops };
+    let mut stack = Stack {
+        top: counter(false),
+        _label: "stack".into(),
+        bottom: counter(false),
+    };
+
+    assert!(!stack.bubble(&mut Event::RedrawRequested).handled);
+    assert_eq!((stack.top.count, stack.bottom.count), (1, 1));
+
+    stack.top.stops = true;
+    assert!(stack.bubble(&mut Event::RedrawRequested).handled);
+    assert_eq!((stack.top.count, stack.bottom.count), (2, 1));
+}
+
+#[derive(Bubble, Element)]
+#[element(effect = Draw)]
+struct Leaf(&'static str);
+
+impl From<&Leaf> for Draw {
+    fn from(Leaf(name): &Leaf) -> Self {
+        Draw(name)
+    }
+}
+
+#[derive(Bubble, Element)]
+enum Screen {
+    Loading(Leaf),
+    Ready { header: Leaf, body: Leaf },
+    Blank,
+}
+
+#[test]
+fn enum_elements_show_the_effects_of_their_variant() {
+    let drawn = |screen: &Screen| {
+        let mut log = Log(Vec::new());
+        Element::<HeadlessEnvironment>::effect(screen).drive_thru(&mut log);
+        log.0
+    };
+
+    assert_eq!(drawn(&Screen::Loading(Leaf("spinner"))), ["spinner"]);
+    assert_eq!(
+        drawn(&Screen::Ready {
+            header: Leaf("header"),
+            body: Leaf("body"),
+        }),
+        ["header", "body"]
+    );
+    assert!(drawn(&Screen::Blank).is_empty());
+}
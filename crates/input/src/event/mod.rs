@@ -166,6 +166,8 @@ pub enum ImeEvent {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum KeyboardEvent {
     Key(KeyEvent),
+    /// The set of modifier keys being held down changed.
+    ModifiersChanged(Modifiers),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -178,6 +180,40 @@ pub struct KeyEvent {
     /// The text representation of this event.
     pub text_repr: Option<SmolStr>,
 
+    /// Which key this event is about, for keys that have a meaning
+    /// other than the text they produce (or that produce no text at all).
+    pub named_key: Option<NamedKey>,
+
     /// The state of this key event.
     pub button_state: ButtonState,
 }
+
+/// Keys that are identified by what they do, rather than by the text they produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NamedKey {
+    Tab,
+    Enter,
+    Escape,
+    Space,
+    Backspace,
+    Delete,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+}
+
+/// The modifier keys being held down.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    /// The "Windows" key, or "Command" on Apple platforms.
+    pub super_key: bool,
+}
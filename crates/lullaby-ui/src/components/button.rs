@@ -13,16 +13,24 @@ static BUTTON_COLOR: Srgba = Srgba::new(255.0, 217.0, 179.0, 255.0);
 static BUTTON_COLOR_HOVER: Srgba = Srgba::new(235.0, 189.0, 143.0, 255.0);
 
 /// This is what `text_color` gets overriden with in a cascading context.
-#[allow(unused)]
 static BUTTON_TEXT_COLOR: Srgba = Srgba::new(175.0, 90.0, 16.0, 255.0);
 
 /// A simple button which can be clicked to trigger some `effect`.
 /// The button supports a `label` component which will be displayed inside the button
+///
+/// If the cascading context says the UI is disabled, the button does nothing when clicked.
 pub fn Button(mut label: impl Ui, effect: impl Effect + 'static) -> impl Ui {
     let is_hovered: Mutable<bool> = Mutable::default();
 
     ItemBox::new(move |hx| {
-        let tap = Tap::new(hx.rect, effect.clone()).with_hover_state(is_hovered.clone());
+        let is_disabled = hx.context.disabled;
+        let mut effect = effect.clone();
+        let effect = move || {
+            if !is_disabled {
+                effect.apply()
+            }
+        };
+        let tap = Tap::new(hx.rect, effect).with_hover_state(is_hovered.clone());
 
         let rect = is_hovered
             .signal_ref(move |is_hovered| {
@@ -36,9 +44,10 @@ pub fn Button(mut label: impl Ui, effect: impl Effect + 'static) -> impl Ui {
 
         let label_hints = ParentHints {
             rect: hx.rect.inflate(Size2::new(-1.0, -1.0)),
+            context: hx.context.with_text_color(BUTTON_TEXT_COLOR / 255.0),
             ..hx
         };
-        let _ = label.prepare(label_hints);
+        let _ = label.prepare(label_hints.clone());
         let label = label.place(label_hints);
 
         list_internal![tap, rect, label]
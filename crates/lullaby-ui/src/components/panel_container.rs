@@ -41,4 +41,12 @@ where
             self.item.place(parent_hints)
         ]
     }
+
+    fn height_for_width(
+        &mut self,
+        width: f32,
+        parent_hints: ParentHints,
+    ) -> f32 {
+        self.item.height_for_width(width, parent_hints)
+    }
 }
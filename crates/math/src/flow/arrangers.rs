@@ -286,6 +286,110 @@ pub fn arrange_stretchy_rects_with_minimum_sizes_dirty_alloc<
     res
 }
 
+/// Like [`arrange_stretchy_rects_with_minimum_sizes_dirty_alloc`],
+/// but elements also have a maximum size they won't grow past.
+///
+/// A maximum smaller than the minimum is ignored in favour of the minimum.
+/// If every stretchy element reaches its maximum before the container is filled,
+/// the remaining space is left empty at the end of the container.
+pub fn arrange_stretchy_rects_with_size_limits<Num: Float + core::iter::Sum>(
+    t: Num,
+    w: &[Num],
+    m: &[Num],
+    x: &[Num],
+    tol: Num,
+) -> Vec<Num> {
+    let total_m: Num = m.iter().copied().sum();
+    let total_w: Num = w.iter().copied().sum();
+
+    if total_m >= t || total_w <= Num::zero() {
+        return m.to_vec();
+    }
+
+    let t_inv_w = t / total_w;
+    let size_at = |equ: Num, weight: Num, min: Num, max: Num| {
+        (weight * t_inv_w * equ).min(max).max(min)
+    };
+    let sum_at = |equ: Num| -> Num {
+        w.iter()
+            .zip(m.iter().zip(x.iter()))
+            .map(|(&weight, (&min, &max))| size_at(equ, weight, min, max))
+            .sum()
+    };
+
+    // How big the elements get if they stretch as much as they can.
+    let saturated: Vec<Num> = w
+        .iter()
+        .zip(m.iter().zip(x.iter()))
+        .map(|(&weight, (&min, &max))| {
+            if weight > Num::zero() {
+                max.max(min)
+            } else {
+                min
+            }
+        })
+        .collect();
+    let is_saturated = saturated.iter().copied().sum::<Num>() <= t;
+
+    let float_sizes: Vec<Num> = if is_saturated {
+        saturated
+    } else {
+        // Unlike the minima-only version, elements can stop growing,
+        // so the equilibrium might be past `t`. Look for an upper bound first.
+        let mut equ_0 = Num::zero();
+        let mut equ_1 = Num::one();
+        while sum_at(equ_1) < t {
+            equ_0 = equ_1;
+            equ_1 = equ_1 * Num::from(2).unwrap();
+        }
+
+        let mut equ = equ_1;
+        for _ in 0..64 {
+            equ = (equ_0 + equ_1) / Num::from(2).unwrap();
+            let sum = sum_at(equ);
+
+            if (t - sum).abs() < tol {
+                break;
+            }
+
+            if t > sum {
+                equ_0 = equ;
+            } else {
+                equ_1 = equ;
+            }
+        }
+
+        w.iter()
+            .zip(m.iter().zip(x.iter()))
+            .map(|(&weight, (&min, &max))| size_at(equ, weight, min, max))
+            .collect()
+    };
+
+    let mut s_off = Num::zero();
+    let mut s_off_px = Num::zero();
+    let mut res: Vec<Num> = float_sizes
+        .into_iter()
+        .map(|float_size| {
+            s_off = s_off + float_size;
+            let next_off_px = s_off.round();
+            let pixel_size = next_off_px - s_off_px;
+            s_off_px = next_off_px;
+            pixel_size
+        })
+        .collect();
+
+    // Only a filled container has rounding errors to make up for.
+    let diff = t.round() - s_off_px;
+    if !is_saturated
+        && diff != Num::zero()
+        && let Some(last) = res.last_mut()
+    {
+        *last = *last + diff;
+    }
+
+    res
+}
+
 pub fn arrange_stretchy_rects_with_minimum_sizes_dirty_alloc_alt<
     Num: Float + core::iter::Sum,
 >(
@@ -2,7 +2,7 @@ use crate::Tui;
 use crate::render::present_canvas_to_terminal;
 use crate::runner::{TerminalBlueprintResources, TerminalEnvironment};
 use core::pin::Pin;
-use core::task::{Context, Poll};
+use core::task::{Context, Poll, Waker};
 use futures_signals::signal::Mutable;
 use futures_signals::signal::{Signal, SignalExt};
 use pin_project::pin_project;
@@ -11,8 +11,11 @@ use ui_composer_core::app::composition::algebra::Bubble;
 use ui_composer_core::app::composition::effects::signal::{
     IntoBlueprint, React,
 };
+use ui_composer_core::app::composition::elements::response::EventResponse;
 use ui_composer_core::app::composition::elements::{Blueprint, Element};
-use ui_composer_core::app::composition::layout::hints::ParentHints;
+use ui_composer_core::app::composition::layout::hints::{
+    CascadingContext, ParentHints,
+};
 use ui_composer_core::app::composition::visit::DriveThru;
 use ui_composer_input::event::{CursorEvent, Event};
 use ui_composer_math::flow::{CartesianFlow, CurrentFlow};
@@ -28,6 +31,10 @@ pub struct TerminalState {
     pub size: Mutable<Size2>,
     pub mouse_position: Mutable<Option<Point2>>,
     pub render_target: PixelCanvas<TextModePixel>,
+    /// Whether some element asked for a redraw since the last one.
+    pub redraw_requested: bool,
+    /// Wakes the executor polling this terminal, so requested redraws happen promptly.
+    pub(crate) waker: Option<Waker>,
 }
 
 impl<UiBlueprint> Blueprint<TerminalEnvironment>
@@ -52,14 +59,14 @@ pub struct TerminalElement<UiElement> {
     pub ui: UiElement,
 }
 
-impl<UiElement> Bubble<Event, bool> for TerminalElement<UiElement>
+impl<UiElement> Bubble<Event, EventResponse> for TerminalElement<UiElement>
 where
-    UiElement: Bubble<Event, bool>,
+    UiElement: Bubble<Event, EventResponse>,
 {
-    fn bubble(&mut self, cx: &mut Event) -> bool {
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
         if let Event::Resized(new_size) = cx {
             self.state.render_target.resize(new_size.as_());
-            self.state.size.set(*new_size);
+            self.state.size.set_neq(*new_size);
         };
 
         if let Event::Cursor {
@@ -78,7 +85,18 @@ where
             self.state.mouse_position.put(None);
         }
 
-        self.ui.bubble(cx)
+        let response = self.ui.bubble(cx);
+
+        // Terminals have no cursor icons and always capture the mouse,
+        // so only redraw requests are acted upon.
+        if response.request_redraw {
+            self.state.redraw_requested = true;
+            if let Some(waker) = self.state.waker.take() {
+                waker.wake();
+            }
+        }
+
+        response
     }
 }
 
@@ -102,12 +120,15 @@ where
     ) -> Poll<Option<()>> {
         let TerminalElementProj { state, mut ui } = self.project();
 
+        state.waker = Some(cx.waker().clone());
+
         let inner = ui.as_mut().poll(cx, env);
+        let redraw_requested = core::mem::take(&mut state.redraw_requested);
 
         match inner {
-            Poll::Pending => Poll::Pending,
-            Poll::Ready(None) => Poll::Ready(None),
-            Poll::Ready(Some(_)) => {
+            Poll::Pending if !redraw_requested => Poll::Pending,
+            Poll::Ready(None) if !redraw_requested => Poll::Ready(None),
+            _ => {
                 let ui_effects = ui.effect();
                 state.render_target.clear();
                 let mut vis = TerminalEffectVisitor {
@@ -159,6 +180,8 @@ where
         size: Mutable::new(size.as_()),
         mouse_position: Mutable::new(None),
         render_target,
+        redraw_requested: false,
+        waker: None,
     };
 
     let ui = state
@@ -175,13 +198,18 @@ where
                     current_writing_cross_flow_direction:
                         CartesianFlow::TopToBottom,
                 },
+                context: CascadingContext::default(),
             };
-            // TODO: Listen to and respect the child hints.
-            #[allow(unused)]
-            let child_hints = ui.prepare(parent_hints);
+            // The app gets the whole terminal, unless its hints say otherwise.
+            let child_hints = ui.prepare(parent_hints.clone());
+            // Wrapping content needs fewer lines the wider the terminal is.
+            let width = child_hints.fit(parent_hints.rect.size).width;
+            let height = ui.height_for_width(width, parent_hints.clone());
             let clamped_rect = Rect::new(
                 Point2::ZERO,
-                parent_hints.rect.size.max(child_hints.minimum_size),
+                child_hints
+                    .with_measured_height(height)
+                    .fit(parent_hints.rect.size),
             );
             ui.place(ParentHints {
                 rect: clamped_rect,
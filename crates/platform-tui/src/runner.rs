@@ -5,6 +5,7 @@ use crossterm::cursor::{
 use crossterm::event::{
     DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste,
     EnableMouseCapture, Event as CrosstermEvent, EventStream, KeyCode,
+    KeyModifiers, MouseButton as CrosstermMouseButton, MouseEventKind,
 };
 use crossterm::terminal::{
     DisableLineWrap, EnableLineWrap, EnterAlternateScreen,
@@ -23,12 +24,15 @@ use ui_composer_core::app::runner::Runner;
 use ui_composer_core::app::runner::futures::AsyncExecutor;
 use ui_composer_input::event::{
     ButtonState, CursorEvent, DeviceId, Event, KeyEvent, KeyboardEvent,
-    TouchStage,
+    Modifiers, MouseButton, NamedKey,
 };
 use ui_composer_math::prelude::{Point2, Size2, Vector2};
 
 use crate::nodes::TerminalEffectVisitor;
 
+/// How often the terminal is redrawn, at most.
+const FRAMES_PER_SECOND: f32 = 60.0;
+
 pub struct TerminalEnvironment;
 pub struct TerminalBlueprintResources;
 
@@ -60,6 +64,9 @@ where
         let app = blueprint.make(&res);
         let app = Arc::new(Mutex::new(app));
         let app_e = app.clone();
+        // The terminal reports modifiers with each key,
+        // so changes to them are reported to the app right before the key.
+        let modifiers = Arc::new(Mutex::new(Modifiers::default()));
 
         // Correction for the terminal's way of indexing.
         let top_left_correction = Vector2::new(1.0, 1.0);
@@ -71,19 +78,13 @@ where
                 .filter_map(|e| async { e.ok() })
                 .for_each(move |event| {
                     let app_e = app_e.clone();
+                    let modifiers = modifiers.clone();
                     async move {
-                        if let CrosstermEvent::Key(e) = event
-                            && let KeyCode::Char('q') = e.code
-                        {
-                            let _ = Self::release_terminal(&mut stdout());
-                            std::process::exit(1);
-                        }
-
                         if let CrosstermEvent::Resize(new_width, new_height) =
                             event
                         {
                             let mut l = app_e.lock().unwrap();
-                            l.bubble(&mut Event::Resized(Size2::new(
+                            let _ = l.bubble(&mut Event::Resized(Size2::new(
                                 new_width as f32,
                                 new_height as f32,
                             )));
@@ -91,7 +92,20 @@ where
 
                         if let CrosstermEvent::Key(k) = event {
                             let mut l = app_e.lock().unwrap();
-                            l.bubble(&mut Event::Keyboard {
+
+                            let current_modifiers = into_modifiers(&k);
+                            let mut last_modifiers = modifiers.lock().unwrap();
+                            if *last_modifiers != current_modifiers {
+                                *last_modifiers = current_modifiers;
+                                let _ = l.bubble(&mut Event::Keyboard {
+                                    id: DeviceId(0),
+                                    event: KeyboardEvent::ModifiersChanged(
+                                        current_modifiers,
+                                    ),
+                                });
+                            }
+
+                            let response = l.bubble(&mut Event::Keyboard {
                                 id: DeviceId(0),
                                 event: KeyboardEvent::Key(KeyEvent {
                                     is_implicit: false,
@@ -99,6 +113,7 @@ where
                                         .code
                                         .as_char()
                                         .map(|x| x.to_smolstr()),
+                                    named_key: into_named_key(k.code),
                                     button_state: if k.is_press() {
                                         ButtonState::Pressed
                                     } else {
@@ -106,13 +121,21 @@ where
                                     },
                                 }),
                             });
+
+                            // Unless the app did something with it, 'q' quits.
+                            if !response.handled
+                                && let KeyCode::Char('q') = k.code
+                            {
+                                let _ = Self::release_terminal(&mut stdout());
+                                std::process::exit(1);
+                            }
                         }
 
                         if let CrosstermEvent::Mouse(m) = event {
                             let mut l = app_e.lock().unwrap();
 
                             if m.kind.is_moved() {
-                                l.bubble(&mut Event::Cursor {
+                                let _ = l.bubble(&mut Event::Cursor {
                                     id: DeviceId(0),
                                     event: CursorEvent::Moved {
                                         position: (Point2::<u16>::new(
@@ -125,7 +148,7 @@ where
                             }
 
                             if m.kind.is_drag() {
-                                l.bubble(&mut Event::Cursor {
+                                let _ = l.bubble(&mut Event::Cursor {
                                     id: DeviceId(0),
                                     event: CursorEvent::Moved {
                                         position: (Point2::<u16>::new(
@@ -137,13 +160,19 @@ where
                                 });
                             }
 
-                            if m.kind.is_down() {
-                                l.bubble(&mut Event::Cursor {
+                            if let MouseEventKind::Down(button)
+                            | MouseEventKind::Up(button) = m.kind
+                            {
+                                let _ = l.bubble(&mut Event::Cursor {
                                     id: DeviceId(0),
-                                    event: CursorEvent::Touched {
-                                        finger_id: 0,
-                                        stage: TouchStage::Started,
-                                    },
+                                    event: CursorEvent::Button(
+                                        into_mouse_button(button),
+                                        if m.kind.is_down() {
+                                            ButtonState::Pressed
+                                        } else {
+                                            ButtonState::Released
+                                        },
+                                    ),
                                 });
                             }
                         }
@@ -151,7 +180,9 @@ where
                 })
                 .await;
         };
-        let async_handler = AsyncExecutor::new(app, res, || {}).to_future();
+        let async_handler = AsyncExecutor::new(app, res, || {})
+            .with_frame_rate(FRAMES_PER_SECOND)
+            .to_future();
         let processes = async { join!(event_handler, async_handler) };
         block_on(processes);
 
@@ -196,3 +227,42 @@ where
         Ok(())
     }
 }
+
+fn into_named_key(code: KeyCode) -> Option<NamedKey> {
+    Some(match code {
+        // Terminals report Shift + Tab as its own key.
+        KeyCode::Tab | KeyCode::BackTab => NamedKey::Tab,
+        KeyCode::Enter => NamedKey::Enter,
+        KeyCode::Esc => NamedKey::Escape,
+        KeyCode::Char(' ') => NamedKey::Space,
+        KeyCode::Backspace => NamedKey::Backspace,
+        KeyCode::Delete => NamedKey::Delete,
+        KeyCode::Up => NamedKey::ArrowUp,
+        KeyCode::Down => NamedKey::ArrowDown,
+        KeyCode::Left => NamedKey::ArrowLeft,
+        KeyCode::Right => NamedKey::ArrowRight,
+        KeyCode::Home => NamedKey::Home,
+        KeyCode::End => NamedKey::End,
+        KeyCode::PageUp => NamedKey::PageUp,
+        KeyCode::PageDown => NamedKey::PageDown,
+        _ => return None,
+    })
+}
+
+fn into_modifiers(key: &crossterm::event::KeyEvent) -> Modifiers {
+    Modifiers {
+        shift: key.modifiers.contains(KeyModifiers::SHIFT)
+            || key.code == KeyCode::BackTab,
+        control: key.modifiers.contains(KeyModifiers::CONTROL),
+        alt: key.modifiers.contains(KeyModifiers::ALT),
+        super_key: key.modifiers.contains(KeyModifiers::SUPER),
+    }
+}
+
+fn into_mouse_button(button: CrosstermMouseButton) -> MouseButton {
+    match button {
+        CrosstermMouseButton::Left => MouseButton::Left,
+        CrosstermMouseButton::Right => MouseButton::Right,
+        CrosstermMouseButton::Middle => MouseButton::Middle,
+    }
+}
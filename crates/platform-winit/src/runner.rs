@@ -19,6 +19,9 @@ use winit::event_loop::{
 };
 use winit::window::{Window, WindowAttributes, WindowId};
 
+/// How often the app is polled for changes, at most.
+const FRAMES_PER_SECOND: f32 = 60.0;
+
 // TODO: Add things to this Environment that elements might want to use.
 // In mind I have a GPU allocator for allocating images and textures.
 // This is probably how one requests a window, too.
@@ -90,11 +93,10 @@ where
                             tracing::debug!(
                                 "[Event Handler] New event `{event:?}`. Broadcasting."
                             );
-                            // TODO: Use something with a little more data than a bool.
-                            let event_was_handled = _lock.bubble(&mut event);
+                            let response = _lock.bubble(&mut event);
                             tracing::debug!(
-                                "[Event Handler] The event was {}.",
-                                if event_was_handled {
+                                "[Event Handler] The event was {}. Response: {response:?}",
+                                if response.handled {
                                     "handled"
                                 } else {
                                     "not handled"
@@ -107,8 +109,9 @@ where
                 let res = WinitBlueprintResources {
                     winit_requester: &winit_requester,
                 };
-                let async_handler =
-                    AsyncExecutor::new(app, res, || {}).to_future();
+                let async_handler = AsyncExecutor::new(app, res, || {})
+                    .with_frame_rate(FRAMES_PER_SECOND)
+                    .to_future();
 
                 // TODO: Think very well about how these two tasks will coordinate,
                 // such that one doesn't hog all the resources when running on a single-threaded
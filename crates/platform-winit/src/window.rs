@@ -5,16 +5,18 @@
 //! it will render them to its [WindowRenderTarget].
 
 use pin_project::pin_project;
-use ui_composer_input::event::Event;
+use ui_composer_input::event::{CursorEvent, Event};
 use ui_composer_math::prelude::Size2;
 use winit::dpi::PhysicalSize;
 use winit::window::{Window, WindowAttributes};
 
 use crate::gpu::{Gpu, RenderTarget};
 use crate::runner::{WinitBlueprintResources, WinitEnvironment};
+use crate::winit_uic_conversion::from_cursor_icon;
 use std::sync::Arc;
 use std::task::Poll;
-use ui_composer_core::app::composition::algebra::Bubble;
+use ui_composer_core::app::composition::algebra::{Bubble, Semigroup as _};
+use ui_composer_core::app::composition::elements::response::EventResponse;
 use ui_composer_core::app::composition::elements::{Blueprint, Element};
 
 pub struct WindowBlueprint<UiBlueprint> {
@@ -54,27 +56,52 @@ pub struct WindowElement<Ui> {
     window: Arc<Window>,
 }
 
-impl<Ui> Bubble<Event, bool> for WindowElement<Ui> {
-    fn bubble(&mut self, cx: &mut Event) -> bool {
-        match cx {
+impl<Ui> Bubble<Event, EventResponse> for WindowElement<Ui>
+where
+    Ui: Bubble<Event, EventResponse>,
+{
+    fn bubble(&mut self, cx: &mut Event) -> EventResponse {
+        let window_response = match cx {
             Event::Resized(_extent2) => {
                 /* Store and broadcast this change by setting the window's state. */
-                true
+                EventResponse::handled()
             }
-            Event::CloseRequested => false,
             Event::RedrawRequested => {
                 tracing::debug!("[Window] Ignoring redraw request.");
 
-                false
-            },
-            Event::OcclusionStateChanged(_) => false,
-            Event::FocusStateChanged(_) => false,
-            Event::ScaleFactorChanged(_) => false,
-            Event::ThemeTypeChanged(_) => false,
-            Event::Cursor { .. } => false,
-            Event::Keyboard { .. } => false,
-            Event::Ime(_) => false,
-            Event::File(_) => false,
+                EventResponse::ignored()
+            }
+            _ => EventResponse::ignored(),
+        };
+
+        let response = window_response.combine(self.ui.bubble(cx));
+        self.act_on(cx, &response);
+        response
+    }
+}
+
+impl<Ui> WindowElement<Ui> {
+    /// Does what the elements inside this window asked for in response to an event.
+    fn act_on(&self, event: &Event, response: &EventResponse) {
+        // Cursor icons are decided anew every time the cursor moves,
+        // so elements that stop asking for an icon get the default one back.
+        if let Event::Cursor {
+            event: CursorEvent::Moved { .. },
+            ..
+        } = event
+        {
+            self.window.set_cursor(from_cursor_icon(
+                response.cursor_icon.unwrap_or_default(),
+            ));
+        }
+
+        // Pointer captures and focus requests are about elements, not the window,
+        // so they're left for a `PointerScopeBlueprint` and a `FocusScopeBlueprint`
+        // inside the app to fulfill. The OS cursor is never grabbed, and the window
+        // doesn't take focus from other apps.
+
+        if response.request_redraw {
+            self.window.request_redraw();
         }
     }
 }
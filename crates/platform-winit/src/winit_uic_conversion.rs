@@ -1,8 +1,10 @@
+use ui_composer_core::app::composition::elements::response::CursorIcon;
 use {
     smol_str::SmolStr,
     ui_composer_input::event::{
         ButtonState, DeviceId, FileDragAndDropEvent, ImeEvent, KeyEvent,
-        KeyboardEvent, MouseButton, ScrollOffset, ThemeType, TouchStage,
+        KeyboardEvent, Modifiers, MouseButton, NamedKey, ScrollOffset,
+        ThemeType, TouchStage,
     },
     ui_composer_math::prelude::Vector2,
     winit::event::{MouseScrollDelta, TouchPhase, WindowEvent},
@@ -186,6 +188,7 @@ pub fn into_event(value: WindowEvent) -> Result<Event, ()> {
             event: KeyboardEvent::Key(KeyEvent {
                 is_implicit: is_synthetic,
                 text_repr: event.text.map(|s| SmolStr::from(s.as_str())),
+                named_key: into_named_key(&event.logical_key),
                 button_state: into_button_state(event.state),
             }),
         }),
@@ -209,8 +212,14 @@ pub fn into_event(value: WindowEvent) -> Result<Event, ()> {
             Ok(Event::OcclusionStateChanged(is_occluded))
         }
 
+        WindowEvent::ModifiersChanged(modifiers) => Ok(Event::Keyboard {
+            id: DeviceId(0),
+            event: KeyboardEvent::ModifiersChanged(into_modifiers(
+                modifiers.state(),
+            )),
+        }),
+
         // MARK: Unsupported
-        WindowEvent::ModifiersChanged(_modifiers) => Err(()),
         WindowEvent::ActivationTokenDone {
             serial: _,
             token: _,
@@ -229,9 +238,62 @@ fn into_to_touch_stage(value: TouchPhase) -> TouchStage {
     }
 }
 
+fn into_named_key(value: &winit::keyboard::Key) -> Option<NamedKey> {
+    use winit::keyboard::{Key, NamedKey as Winit};
+
+    let Key::Named(named_key) = value else {
+        return None;
+    };
+
+    Some(match named_key {
+        Winit::Tab => NamedKey::Tab,
+        Winit::Enter => NamedKey::Enter,
+        Winit::Escape => NamedKey::Escape,
+        Winit::Space => NamedKey::Space,
+        Winit::Backspace => NamedKey::Backspace,
+        Winit::Delete => NamedKey::Delete,
+        Winit::ArrowUp => NamedKey::ArrowUp,
+        Winit::ArrowDown => NamedKey::ArrowDown,
+        Winit::ArrowLeft => NamedKey::ArrowLeft,
+        Winit::ArrowRight => NamedKey::ArrowRight,
+        Winit::Home => NamedKey::Home,
+        Winit::End => NamedKey::End,
+        Winit::PageUp => NamedKey::PageUp,
+        Winit::PageDown => NamedKey::PageDown,
+        _ => return None,
+    })
+}
+
+fn into_modifiers(value: winit::keyboard::ModifiersState) -> Modifiers {
+    Modifiers {
+        shift: value.shift_key(),
+        control: value.control_key(),
+        alt: value.alt_key(),
+        super_key: value.super_key(),
+    }
+}
+
 fn into_button_state(value: ElementState) -> ButtonState {
     match value {
         ElementState::Pressed => ButtonState::Pressed,
         ElementState::Released => ButtonState::Released,
     }
 }
+
+pub fn from_cursor_icon(value: CursorIcon) -> winit::window::CursorIcon {
+    use winit::window::CursorIcon as Winit;
+
+    match value {
+        CursorIcon::Pointer => Winit::Pointer,
+        CursorIcon::Text => Winit::Text,
+        CursorIcon::Grab => Winit::Grab,
+        CursorIcon::Grabbing => Winit::Grabbing,
+        CursorIcon::Move => Winit::Move,
+        CursorIcon::EwResize => Winit::EwResize,
+        CursorIcon::NsResize => Winit::NsResize,
+        CursorIcon::Crosshair => Winit::Crosshair,
+        CursorIcon::NotAllowed => Winit::NotAllowed,
+        CursorIcon::Wait => Winit::Wait,
+        _ => Winit::Default,
+    }
+}
@@ -0,0 +1,254 @@
+//! # Collections
+//!
+//! [`Slot`]s for single items of reactive collections,
+//! so that an editor for one item can be given just its item.
+//!
+//! ```rust,ignore
+//! let todos = MutableVec::new_with_values(vec!["Buy milk".to_string()]);
+//! let first = todos.slot_at(0);
+//! first.put("Buy oat milk".into());
+//! ```
+
+use crate::{Observable, Slot, State};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_signals::signal::{Signal, SignalExt as _};
+use futures_signals::signal_map::{MutableBTreeMap, SignalMapExt as _};
+use futures_signals::signal_vec::{MutableSignalVec, MutableVec, SignalVec};
+use pin_project::pin_project;
+
+/// Collections with a [`Slot`] for each of their items.
+pub trait SlotAt<Key> {
+    type Slot: Slot;
+
+    /// The slot for the item at `key`.
+    fn slot_at(&self, key: Key) -> Self::Slot;
+}
+
+/// The item at some index of a [`MutableVec`].
+///
+/// Its value is `None` while the index is out of bounds.
+/// Putting `None` in it removes the item, and putting `Some` right past
+/// the end of the vec pushes it.
+///
+/// The slot follows the index, not the item:
+/// after an insertion before it, it holds the item that was before it.
+#[derive(Clone)]
+pub struct VecIndex<A> {
+    vec: MutableVec<A>,
+    index: usize,
+}
+
+impl<A> SlotAt<usize> for MutableVec<A>
+where
+    A: Clone,
+{
+    type Slot = VecIndex<A>;
+
+    fn slot_at(&self, index: usize) -> Self::Slot {
+        VecIndex {
+            vec: self.clone(),
+            index,
+        }
+    }
+}
+
+impl<A> Slot for VecIndex<A>
+where
+    A: Clone,
+{
+    type Item = Option<A>;
+
+    fn put(&self, value: Self::Item) {
+        self.modify(|item| *item = value.clone());
+    }
+
+    fn take(&self) -> Self::Item
+    where
+        Self::Item: Copy,
+    {
+        self.vec.lock_ref().get(self.index).cloned()
+    }
+
+    fn modify<F>(&self, predicate: F)
+    where
+        F: Fn(&mut Self::Item),
+    {
+        // Items of a `MutableVec` are replaced, not changed in place,
+        // so that its signals see the change.
+        let mut vec = self.vec.lock_mut();
+        let mut value = vec.get(self.index).cloned();
+        predicate(&mut value);
+
+        match value {
+            Some(value) if self.index < vec.len() => {
+                vec.set_cloned(self.index, value);
+            }
+            Some(value) if self.index == vec.len() => {
+                vec.push_cloned(value);
+            }
+            None if self.index < vec.len() => {
+                vec.remove(self.index);
+            }
+            _ => {}
+        }
+    }
+
+    fn with_ref<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&Self::Item) -> R,
+    {
+        f(&self.vec.lock_ref().get(self.index).cloned())
+    }
+}
+
+impl<A: Clone> State for VecIndex<A> {}
+
+impl<A> Observable for VecIndex<A>
+where
+    A: Clone,
+{
+    fn signal_ref<B, F>(&self, f: F) -> impl Signal<Item = B> + use<A, B, F>
+    where
+        F: FnMut(&Self::Item) -> B,
+    {
+        VecIndexSignal {
+            changes: self.vec.signal_vec_cloned(),
+            vec: self.vec.clone(),
+            index: self.index,
+            f,
+        }
+    }
+}
+
+/// Looks at the item again whenever the vec changes,
+/// without keeping a copy of the whole vec around.
+#[pin_project]
+struct VecIndexSignal<A, F> {
+    #[pin]
+    changes: MutableSignalVec<A>,
+    vec: MutableVec<A>,
+    index: usize,
+    f: F,
+}
+
+impl<A, B, F> Signal for VecIndexSignal<A, F>
+where
+    A: Clone,
+    F: FnMut(&Option<A>) -> B,
+{
+    type Item = B;
+
+    fn poll_change(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        let mut changed = false;
+        loop {
+            match this.changes.as_mut().poll_vec_change(cx) {
+                Poll::Ready(Some(_)) => changed = true,
+                Poll::Ready(None) if !changed => return Poll::Ready(None),
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+
+        if changed {
+            let item = this.vec.lock_ref().get(*this.index).cloned();
+            Poll::Ready(Some((this.f)(&item)))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// The entry for some key of a [`MutableBTreeMap`].
+///
+/// Its value is `None` while the key is vacant,
+/// and putting `None` in it removes the key.
+#[derive(Clone)]
+pub struct MapEntry<K, V> {
+    map: MutableBTreeMap<K, V>,
+    key: K,
+}
+
+impl<K, V> SlotAt<K> for MutableBTreeMap<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    type Slot = MapEntry<K, V>;
+
+    fn slot_at(&self, key: K) -> Self::Slot {
+        MapEntry {
+            map: self.clone(),
+            key,
+        }
+    }
+}
+
+impl<K, V> Slot for MapEntry<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    type Item = Option<V>;
+
+    fn put(&self, value: Self::Item) {
+        self.modify(|entry| *entry = value.clone());
+    }
+
+    fn take(&self) -> Self::Item
+    where
+        Self::Item: Copy,
+    {
+        self.map.lock_ref().get(&self.key).cloned()
+    }
+
+    fn modify<F>(&self, predicate: F)
+    where
+        F: Fn(&mut Self::Item),
+    {
+        let mut map = self.map.lock_mut();
+        let mut value = map.get(&self.key).cloned();
+        predicate(&mut value);
+
+        match value {
+            Some(value) => {
+                map.insert_cloned(self.key.clone(), value);
+            }
+            None => {
+                map.remove(&self.key);
+            }
+        }
+    }
+
+    fn with_ref<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&Self::Item) -> R,
+    {
+        f(&self.map.lock_ref().get(&self.key).cloned())
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> State for MapEntry<K, V> {}
+
+impl<K, V> Observable for MapEntry<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    fn signal_ref<B, F>(
+        &self,
+        mut f: F,
+    ) -> impl Signal<Item = B> + use<K, V, B, F>
+    where
+        F: FnMut(&Self::Item) -> B,
+    {
+        self.map
+            .signal_map_cloned()
+            .key_cloned(self.key.clone())
+            .map(move |value| f(&value))
+    }
+}
@@ -0,0 +1,180 @@
+//! # Clocks
+//!
+//! Animations don't look at the wall clock themselves, they're told what time it is
+//! (and when to draw the next frame) by a [`Clock`].
+//! So do effects that wait, like [debounced](crate::effect::Effect::debounce_with) ones.
+//!
+//! A [`RealClock`] follows the wall clock, and a [`ManualClock`] only moves when told to,
+//! which is useful for stepping through animations frame by frame in tests.
+
+use core::future::Future;
+use core::task::{Poll, Waker};
+use futures_time::{
+    task,
+    time::{Duration, Instant},
+};
+use std::sync::{Arc, Mutex};
+
+/// Tells animations the time and paces their frames.
+pub trait Clock {
+    /// The current time, according to this clock.
+    fn now(&self) -> Instant;
+
+    /// Waits until it's time for the next frame.
+    fn next_frame(&self) -> impl Future<Output = ()>;
+
+    /// Calls `f` once this clock reaches `time`.
+    ///
+    /// By default, `f` is called from a background thread
+    /// which sleeps for as long as the clock says is left.
+    fn call_at(&self, time: Instant, f: impl FnOnce() + Send + 'static) {
+        let delay = time.saturating_duration_since(*self.now());
+        std::thread::spawn(move || {
+            std::thread::sleep(delay);
+            f();
+        });
+    }
+}
+
+/// A [`Clock`] that follows the wall clock,
+/// with frames a fixed amount of time apart.
+#[derive(Debug, Clone, Copy)]
+pub struct RealClock {
+    frame_duration: Duration,
+}
+
+impl RealClock {
+    pub fn new(frame_duration: Duration) -> Self {
+        Self { frame_duration }
+    }
+}
+
+impl Default for RealClock {
+    /// A clock at (roughly) 60 frames per second.
+    fn default() -> Self {
+        Self::new(Duration::from_millis(16))
+    }
+}
+
+impl Clock for RealClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn next_frame(&self) -> impl Future<Output = ()> {
+        let sleep = task::sleep(self.frame_duration);
+        async move {
+            sleep.await;
+        }
+    }
+}
+
+/// A [`Clock`] that only moves forward when [advanced](ManualClock::advance).
+///
+/// Every advance is a new frame, and calls whatever was [waiting](Clock::call_at) for it
+/// right away, from the thread that advanced it.
+///
+/// ```rust,ignore
+/// let clock = ManualClock::new();
+/// let animation = lerp(10.0, Duration::from_secs(1))
+///     .animate_value_with(clock.clone(), value.clone());
+///
+/// // ... poll the animation ...
+/// clock.advance(Duration::from_millis(500));
+/// // ... poll it again, `value` should be halfway there.
+/// ```
+#[derive(Clone)]
+pub struct ManualClock {
+    state: Arc<Mutex<ManualClockState>>,
+}
+
+struct ManualClockState {
+    now: std::time::Instant,
+    frame: u64,
+    waiting: Vec<Waker>,
+    calls: Vec<(std::time::Instant, Box<dyn FnOnce() + Send>)>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ManualClockState {
+                now: std::time::Instant::now(),
+                frame: 0,
+                waiting: Vec::new(),
+                calls: Vec::new(),
+            })),
+        }
+    }
+
+    /// Moves the clock forward by `duration`, starting a new frame.
+    pub fn advance(&self, duration: Duration) {
+        let waiting = {
+            let mut state = self.state.lock().unwrap();
+            state.now += *duration;
+            state.frame += 1;
+            core::mem::take(&mut state.waiting)
+        };
+
+        waiting.into_iter().for_each(Waker::wake);
+
+        // Calls can ask for more calls, which might be due already.
+        while let Some(f) = self.take_due_call() {
+            f();
+        }
+    }
+
+    fn take_due_call(&self) -> Option<Box<dyn FnOnce() + Send>> {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now;
+        let index = state.calls.iter().position(|(time, _)| *time <= now)?;
+        Some(state.calls.remove(index).1)
+    }
+}
+
+impl core::fmt::Debug for ManualClock {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("ManualClock")
+            .field("now", &state.now)
+            .field("frame", &state.frame)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.state.lock().unwrap().now.into()
+    }
+
+    fn next_frame(&self) -> impl Future<Output = ()> {
+        let state = self.state.clone();
+        let next_frame = state.lock().unwrap().frame + 1;
+
+        core::future::poll_fn(move |cx| {
+            let mut state = state.lock().unwrap();
+            if state.frame >= next_frame {
+                Poll::Ready(())
+            } else {
+                state.waiting.push(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
+
+    fn call_at(&self, time: Instant, f: impl FnOnce() + Send + 'static) {
+        let mut state = self.state.lock().unwrap();
+        if *time <= state.now {
+            drop(state);
+            f();
+        } else {
+            state.calls.push((*time, Box::new(f)));
+        }
+    }
+}
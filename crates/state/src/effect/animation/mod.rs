@@ -5,12 +5,11 @@
 
 use crate::Slot;
 use core::{fmt::Debug, future::Future};
-use futures_time::{
-    task,
-    time::{Duration, Instant},
-};
+use futures_time::time::{Duration, Instant};
 
+pub mod clock;
 pub mod spring;
+use clock::{Clock, RealClock};
 pub use futures_time;
 
 use core::ops::{Add, Mul, Sub};
@@ -108,38 +107,53 @@ pub trait Animation {
     /// Consumes this [Animation] and produces a future that completes
     /// when the animation is finished.
     fn animate_from<F>(
+        self,
+        f: F,
+        initial_value: Self::Item,
+    ) -> impl Future<Output = ()>
+    where
+        Self::Item: Copy,
+        Self: Sized,
+        F: FnMut(Self::Item),
+    {
+        self.animate_from_with(RealClock::default(), f, initial_value)
+    }
+
+    /// Same as [Self::animate_from], but frames are paced by `clock`.
+    fn animate_from_with<C, F>(
         mut self,
+        clock: C,
         mut f: F,
         initial_value: Self::Item,
     ) -> impl Future<Output = ()>
     where
         Self::Item: Copy,
         Self: Sized,
+        C: Clock,
         F: FnMut(Self::Item),
     {
-        let start = Instant::now();
-        let mut last_frame = Instant::now();
         async move {
+            let start = clock.now();
+            let mut last_frame = start;
             loop {
-                let delta = last_frame.elapsed().into();
-                let poll = self
-                    .process(initial_value, AnimationFrame { start, delta });
+                let now = clock.now();
+                let delta = now.saturating_duration_since(*last_frame).into();
+                last_frame = now;
+
+                let poll = self.process(
+                    initial_value,
+                    AnimationFrame { start, now, delta },
+                );
 
                 match poll {
-                    Poll::Ongoing(frame) => {
-                        last_frame = Instant::now();
-                        f(frame);
-                    }
+                    Poll::Ongoing(frame) => f(frame),
                     Poll::Finished(frame) => {
                         f(frame);
                         break;
                     }
                 }
 
-                task::sleep(
-                    Duration::from_millis(16) - last_frame.elapsed().into(),
-                )
-                .await;
+                clock.next_frame().await;
             }
         }
     }
@@ -151,9 +165,28 @@ pub trait Animation {
         Self::Item: Copy,
         Self: Sized,
         S: Slot<Item = Self::Item> + 'static,
+    {
+        self.animate_value_with(RealClock::default(), state)
+    }
+
+    /// Same as [Self::animate_value], but frames are paced by `clock`.
+    fn animate_value_with<C, S>(
+        self,
+        clock: C,
+        state: S,
+    ) -> impl Future<Output = ()>
+    where
+        Self::Item: Copy,
+        Self: Sized,
+        C: Clock,
+        S: Slot<Item = Self::Item> + 'static,
     {
         let initial_value = state.take();
-        self.animate_from(move |frame| state.put(frame), initial_value)
+        self.animate_from_with(
+            clock,
+            move |frame| state.put(frame),
+            initial_value,
+        )
     }
 
     /// Same as [Self::animate_value] but animates with a slot reference...
@@ -170,12 +203,21 @@ pub trait Animation {
 
 #[derive(Debug, Copy, Clone)]
 pub struct AnimationFrame {
-    /// Time since the beginning of the stream.
+    /// When the stream began.
     pub start: Instant,
+    /// When this frame began, according to the animation's [`Clock`].
+    pub now: Instant,
     /// Time since the last frame.
     pub delta: Duration,
 }
 
+impl AnimationFrame {
+    /// Time since the beginning of the stream.
+    pub fn elapsed(&self) -> Duration {
+        self.now.saturating_duration_since(*self.start).into()
+    }
+}
+
 #[derive(Debug)]
 pub enum Poll<TItem> {
     Ongoing(TItem),
@@ -207,7 +249,7 @@ where
                 match poll {
                     Poll::Ongoing(frame) => Poll::Ongoing(frame),
                     Poll::Finished(frame) => {
-                        let stream_a_end = Instant::now();
+                        let stream_a_end = frame_params.now;
                         self.stream_a_finished = Some((frame, stream_a_end));
                         self.stream_b.process(
                             frame,
@@ -309,13 +351,14 @@ impl<Item: Lerp> Animation for LinearInterpolate<Item> {
     where
         Self::Item: Copy,
     {
-        if frame_params.start.elapsed() >= self.duration.into() {
+        let elapsed = frame_params.elapsed();
+
+        if elapsed >= self.duration {
             Poll::Finished(self.to)
         } else {
             Poll::Ongoing(initial_value.linear_interpolate(
                 self.to,
-                frame_params.start.elapsed().as_secs_f32()
-                    / self.duration.as_secs_f32(),
+                elapsed.as_secs_f32() / self.duration.as_secs_f32(),
             ))
         }
     }
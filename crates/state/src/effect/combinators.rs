@@ -0,0 +1,158 @@
+//! Effects made of other effects, see the provided methods of [`Effect`].
+
+use crate::Slot;
+use crate::effect::Effect;
+use crate::effect::animation::clock::Clock;
+use futures_signals::signal::{Signal, SignalExt as _};
+use futures_time::time::{Duration, Instant};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// See [`Effect::when`].
+///
+/// Clones share the condition's signal.
+pub struct When<A, S> {
+    pub(crate) effect: A,
+    pub(crate) condition: Arc<Mutex<Condition<S>>>,
+}
+
+impl<A: Clone, S> Clone for When<A, S> {
+    fn clone(&self) -> Self {
+        Self {
+            effect: self.effect.clone(),
+            condition: self.condition.clone(),
+        }
+    }
+}
+
+/// The latest value of a [`When`]'s signal.
+pub(crate) struct Condition<S> {
+    pub(crate) signal: Pin<Box<S>>,
+    pub(crate) value: bool,
+}
+
+impl<S: Signal<Item = bool>> Condition<S> {
+    /// Catches up with the signal, without waiting for it.
+    fn get(&mut self) -> bool {
+        let mut cx = Context::from_waker(Waker::noop());
+        while let Poll::Ready(Some(value)) =
+            self.signal.poll_change_unpin(&mut cx)
+        {
+            self.value = value;
+        }
+        self.value
+    }
+}
+
+impl<A, S> Effect for When<A, S>
+where
+    A: Effect,
+    S: Signal<Item = bool> + Send,
+{
+    fn apply(&mut self) {
+        if self.condition.lock().unwrap().get() {
+            self.effect.apply();
+        }
+    }
+}
+
+/// See [`Effect::debounce_with`].
+#[derive(Clone)]
+pub struct Debounce<A, C> {
+    pub(crate) effect: A,
+    pub(crate) duration: Duration,
+    pub(crate) clock: C,
+    /// When the effect is due, while it is waiting to be applied.
+    pub(crate) deadline: Arc<Mutex<Option<Instant>>>,
+}
+
+impl<A, C> Debounce<A, C>
+where
+    A: Effect + 'static,
+    C: Clock + Clone + Send + Sync + 'static,
+{
+    /// Applies the effect if it's due, or waits for it to be.
+    ///
+    /// Only one call is ever waiting on the clock, applications in the meantime
+    /// only move the deadline, which this call then catches up with.
+    fn wait(mut self, time: Instant) {
+        let clock = self.clock.clone();
+        clock.call_at(time, move || {
+            let mut deadline = self.deadline.lock().unwrap();
+            match *deadline {
+                Some(time) if *self.clock.now() < *time => {
+                    drop(deadline);
+                    self.wait(time);
+                }
+                _ => {
+                    *deadline = None;
+                    drop(deadline);
+                    self.effect.apply();
+                }
+            }
+        });
+    }
+}
+
+impl<A, C> Effect for Debounce<A, C>
+where
+    A: Effect + 'static,
+    C: Clock + Clone + Send + Sync + 'static,
+{
+    fn apply(&mut self) {
+        let time = self.clock.now() + self.duration;
+        let was_waiting = self.deadline.lock().unwrap().replace(time).is_some();
+        if !was_waiting {
+            self.clone().wait(time);
+        }
+    }
+}
+
+/// See [`Effect::throttle_with`].
+#[derive(Clone)]
+pub struct Throttle<A, C> {
+    pub(crate) effect: A,
+    pub(crate) duration: Duration,
+    pub(crate) clock: C,
+    pub(crate) last_applied: Arc<Mutex<Option<Instant>>>,
+}
+
+impl<A, C> Effect for Throttle<A, C>
+where
+    A: Effect,
+    C: Clock + Clone + Send + Sync,
+{
+    fn apply(&mut self) {
+        {
+            let now = self.clock.now();
+            let mut last_applied = self.last_applied.lock().unwrap();
+            if last_applied.is_some_and(|last| *now < *(last + self.duration)) {
+                return;
+            }
+            *last_applied = Some(now);
+        }
+
+        self.effect.apply();
+    }
+}
+
+/// See [`Effect::map_state`].
+#[derive(Clone)]
+pub struct MapState<A, S, F> {
+    pub(crate) effect: A,
+    pub(crate) state: S,
+    pub(crate) f: F,
+}
+
+impl<A, S, F> Effect for MapState<A, S, F>
+where
+    A: Effect,
+    S: Slot + Clone + Send + Sync,
+    F: Fn(&mut S::Item) + Clone + Send + Sync,
+{
+    fn apply(&mut self) {
+        self.state.modify(&self.f);
+        self.effect.apply();
+    }
+}
@@ -1,14 +1,120 @@
 //! # Effect
 //!
 //! An effect is a change in state.
+//!
+//! Effects can be composed, so that, say, a button can "save, then close the dialog":
+//!
+//! ```rust,ignore
+//! Button(Label("Save"), save.then(close_dialog).throttle(Duration::from_secs(1)))
+//! ```
+//!
+//! Tuples of effects are effects too, which apply each of theirs in order.
 
 pub mod animation;
+pub mod combinators;
+
+use crate::Slot;
+use animation::clock::{Clock, RealClock};
+use combinators::{Condition, Debounce, MapState, Throttle, When};
+use futures_signals::signal::Signal;
+use futures_time::time::Duration;
+use std::sync::{Arc, Mutex};
 
 /// Trait that describes an effect — a modification to an environment.
 #[must_use = "effects are lazy and do nothing unless applied"]
 pub trait Effect: Clone + Send + Sync {
     /// Applies the effect.
     fn apply(&mut self);
+
+    /// Applies this effect, then `other`.
+    ///
+    /// This is the same as the tuple `(self, other)`.
+    fn then<B>(self, other: B) -> (Self, B)
+    where
+        Self: Sized,
+        B: Effect,
+    {
+        (self, other)
+    }
+
+    /// Applies this effect only while `condition`'s latest value is `true`.
+    fn when<S>(self, condition: S) -> When<Self, S>
+    where
+        Self: Sized,
+        S: Signal<Item = bool> + Send,
+    {
+        When {
+            effect: self,
+            condition: Arc::new(Mutex::new(Condition {
+                signal: Box::pin(condition),
+                value: false,
+            })),
+        }
+    }
+
+    /// Applies this effect once it stops being applied for `duration`.
+    ///
+    /// The effect is applied later, from another thread.
+    fn debounce(self, duration: Duration) -> Debounce<Self, RealClock>
+    where
+        Self: Sized + 'static,
+    {
+        self.debounce_with(RealClock::default(), duration)
+    }
+
+    /// Same as [Self::debounce], but time is told by `clock`,
+    /// which also applies the effect once it's due (see [`Clock::call_at`]).
+    fn debounce_with<C>(self, clock: C, duration: Duration) -> Debounce<Self, C>
+    where
+        Self: Sized + 'static,
+        C: Clock + Clone + Send + Sync + 'static,
+    {
+        Debounce {
+            effect: self,
+            duration,
+            clock,
+            deadline: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Applies this effect at most once every `duration`,
+    /// ignoring the applications in between.
+    fn throttle(self, duration: Duration) -> Throttle<Self, RealClock>
+    where
+        Self: Sized,
+    {
+        self.throttle_with(RealClock::default(), duration)
+    }
+
+    /// Same as [Self::throttle], but time is told by `clock`.
+    fn throttle_with<C>(self, clock: C, duration: Duration) -> Throttle<Self, C>
+    where
+        Self: Sized,
+        C: Clock + Clone + Send + Sync,
+    {
+        Throttle {
+            effect: self,
+            duration,
+            clock,
+            last_applied: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Changes `state` with `f`, then applies this effect.
+    ///
+    /// Useful for effects that read some state, to prepare it first.
+    fn map_state<S, F>(self, state: S, f: F) -> MapState<Self, S, F>
+    where
+        Self: Sized,
+        S: Slot + Clone + Send + Sync,
+        F: Fn(&mut S::Item) + Clone + Send + Sync,
+    {
+        MapState {
+            effect: self,
+            state,
+            f,
+        }
+    }
 }
 
 pub mod implementations {
@@ -28,4 +134,28 @@ pub mod implementations {
             self.set(Some(()))
         }
     }
+
+    macro_rules! impl_effect_for_tuple {
+        ($($effect:ident $index:tt),+) => {
+            impl<$($effect: Effect),+> Effect for ($($effect,)+) {
+                fn apply(&mut self) {
+                    $(self.$index.apply();)+
+                }
+            }
+        };
+    }
+
+    impl_effect_for_tuple!(A 0, B 1);
+    impl_effect_for_tuple!(A 0, B 1, C 2);
+    impl_effect_for_tuple!(A 0, B 1, C 2, D 3);
+    impl_effect_for_tuple!(A 0, B 1, C 2, D 3, E 4);
+    impl_effect_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+    impl_effect_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+    impl_effect_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+    impl<A: Effect> Effect for Vec<A> {
+        fn apply(&mut self) {
+            self.iter_mut().for_each(Effect::apply);
+        }
+    }
 }
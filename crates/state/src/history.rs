@@ -0,0 +1,312 @@
+//! # History
+//!
+//! An [`UndoStack`] remembers the changes made through the slots it [tracks](UndoStack::track),
+//! so that they can be undone (and redone) later.
+//!
+//! ```rust,ignore
+//! let history = UndoStack::new();
+//! let name = history.track(Mutable::new(String::new()));
+//!
+//! let rename = name.clone().effect(|name| *name = "Ana".into());
+//! let undo = history.undo_effect();
+//!
+//! // Give `rename` to one button and `undo` to another,
+//! // and disable the latter with `history.can_undo()`.
+//! ```
+//!
+//! Every change is undone on its own, unless it was made in a [transaction](UndoStack::transaction).
+
+use crate::effect::Effect;
+use crate::{Observable, Slot, State};
+use futures_signals::signal::{Mutable, Signal};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A history of changes made to some [`Slot`]s, see the [module docs](self).
+#[derive(Clone, Default)]
+pub struct UndoStack {
+    history: Arc<Mutex<History>>,
+    can_undo: Mutable<bool>,
+    can_redo: Mutable<bool>,
+}
+
+#[derive(Default)]
+struct History {
+    undo: Vec<Transaction>,
+    redo: Vec<Transaction>,
+    /// The transaction being recorded, and how many times it was opened.
+    open: Option<(Transaction, usize)>,
+    /// Whether the history is undoing or redoing something,
+    /// in which case it shouldn't record anything.
+    replaying: bool,
+    limit: Option<usize>,
+}
+
+type Transaction = Vec<Change>;
+
+struct Change {
+    undo: Box<dyn Fn() + Send + Sync>,
+    redo: Box<dyn Fn() + Send + Sync>,
+}
+
+impl UndoStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets the oldest transactions once there are more than `limit` of them.
+    pub fn with_limit(self, limit: usize) -> Self {
+        self.history().limit = Some(limit);
+        self
+    }
+
+    /// Wraps `slot` so that changes made through it are recorded in this history.
+    pub fn track<S: Slot>(&self, slot: S) -> Tracked<S> {
+        Tracked {
+            slot,
+            history: self.clone(),
+        }
+    }
+
+    /// Calls `f`, recording all the changes it makes as a single transaction,
+    /// which is undone (or redone) all at once.
+    pub fn transaction<R>(&self, f: impl FnOnce() -> R) -> R {
+        {
+            let mut history = self.history();
+            let (_, depth) = history.open.get_or_insert_with(Default::default);
+            *depth += 1;
+        }
+
+        // Closes the transaction even if `f` panics.
+        struct Close<'a>(&'a UndoStack);
+
+        impl Drop for Close<'_> {
+            fn drop(&mut self) {
+                let mut history = self.0.history();
+                let Some((transaction, depth)) = history.open.as_mut() else {
+                    return;
+                };
+
+                *depth -= 1;
+                if *depth == 0 {
+                    let transaction = std::mem::take(transaction);
+                    history.open = None;
+                    history.push(transaction);
+                    drop(history);
+                    self.0.refresh();
+                }
+            }
+        }
+
+        let _close = Close(self);
+        f()
+    }
+
+    /// An effect that applies `effect` as a single transaction.
+    pub fn transaction_effect(&self, mut effect: impl Effect) -> impl Effect {
+        let history = self.clone();
+        move || history.transaction(|| effect.apply())
+    }
+
+    /// Undoes the last transaction, returning whether there was one.
+    pub fn undo(&self) -> bool {
+        let Some(transaction) = self.history().undo.pop() else {
+            return false;
+        };
+
+        self.replay(|| {
+            transaction.iter().rev().for_each(|change| (change.undo)())
+        });
+        self.history().redo.push(transaction);
+        self.refresh();
+        true
+    }
+
+    /// Redoes the last transaction that was undone, returning whether there was one.
+    pub fn redo(&self) -> bool {
+        let Some(transaction) = self.history().redo.pop() else {
+            return false;
+        };
+
+        self.replay(|| transaction.iter().for_each(|change| (change.redo)()));
+        self.history().undo.push(transaction);
+        self.refresh();
+        true
+    }
+
+    /// An effect that undoes the last transaction.
+    pub fn undo_effect(&self) -> impl Effect {
+        let history = self.clone();
+        move || {
+            history.undo();
+        }
+    }
+
+    /// An effect that redoes the last transaction that was undone.
+    pub fn redo_effect(&self) -> impl Effect {
+        let history = self.clone();
+        move || {
+            history.redo();
+        }
+    }
+
+    /// A signal of whether there's anything to undo.
+    pub fn can_undo(&self) -> impl Signal<Item = bool> + use<> {
+        self.can_undo.signal()
+    }
+
+    /// A signal of whether there's anything to redo.
+    pub fn can_redo(&self) -> impl Signal<Item = bool> + use<> {
+        self.can_redo.signal()
+    }
+
+    /// Forgets everything.
+    pub fn clear(&self) {
+        {
+            let mut history = self.history();
+            history.undo.clear();
+            history.redo.clear();
+        }
+        self.refresh();
+    }
+
+    fn history(&self) -> MutexGuard<'_, History> {
+        self.history.lock().unwrap()
+    }
+
+    fn record(&self, change: Change) {
+        let mut history = self.history();
+        if history.replaying {
+            return;
+        }
+
+        match history.open.as_mut() {
+            Some((transaction, _)) => transaction.push(change),
+            None => {
+                history.push(vec![change]);
+                drop(history);
+                self.refresh();
+            }
+        }
+    }
+
+    fn replay(&self, f: impl FnOnce()) {
+        // Stops replaying even if `f` panics.
+        struct Replaying<'a>(&'a UndoStack);
+
+        impl Drop for Replaying<'_> {
+            fn drop(&mut self) {
+                self.0.history().replaying = false;
+            }
+        }
+
+        self.history().replaying = true;
+        let _replaying = Replaying(self);
+        f();
+    }
+
+    fn refresh(&self) {
+        let (can_undo, can_redo) = {
+            let history = self.history();
+            (!history.undo.is_empty(), !history.redo.is_empty())
+        };
+        self.can_undo.set_neq(can_undo);
+        self.can_redo.set_neq(can_redo);
+    }
+}
+
+impl History {
+    /// Pushes a new transaction, which makes whatever was undone unredoable.
+    fn push(&mut self, transaction: Transaction) {
+        if transaction.is_empty() {
+            return;
+        }
+
+        self.redo.clear();
+        self.undo.push(transaction);
+        if let Some(limit) = self.limit
+            && self.undo.len() > limit
+        {
+            self.undo.drain(..self.undo.len() - limit);
+        }
+    }
+}
+
+/// A [`Slot`] whose changes are recorded by an [`UndoStack`],
+/// made with [`UndoStack::track`].
+#[derive(Clone)]
+pub struct Tracked<S> {
+    slot: S,
+    history: UndoStack,
+}
+
+impl<S> Tracked<S>
+where
+    S: Slot + Clone + Send + Sync + 'static,
+    S::Item: Clone + Send + Sync + 'static,
+{
+    /// Records the change `f` makes to the slot.
+    fn record(&self, f: impl FnOnce(&S)) {
+        let before = self.slot.with_ref(S::Item::clone);
+        f(&self.slot);
+        let after = self.slot.with_ref(S::Item::clone);
+
+        let (undo_slot, redo_slot) = (self.slot.clone(), self.slot.clone());
+        self.history.record(Change {
+            undo: Box::new(move || undo_slot.put(before.clone())),
+            redo: Box::new(move || redo_slot.put(after.clone())),
+        });
+    }
+}
+
+impl<S> Slot for Tracked<S>
+where
+    S: Slot + Clone + Send + Sync + 'static,
+    S::Item: Clone + Send + Sync + 'static,
+{
+    type Item = S::Item;
+
+    fn put(&self, value: Self::Item) {
+        self.record(|slot| slot.put(value));
+    }
+
+    fn take(&self) -> Self::Item
+    where
+        Self::Item: Copy,
+    {
+        self.slot.take()
+    }
+
+    fn modify<F>(&self, predicate: F)
+    where
+        F: Fn(&mut Self::Item),
+    {
+        self.record(|slot| slot.modify(predicate));
+    }
+
+    fn with_ref<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&Self::Item) -> R,
+    {
+        self.slot.with_ref(f)
+    }
+}
+
+impl<S> State for Tracked<S>
+where
+    S: Slot + Clone + Send + Sync + 'static,
+    S::Item: Clone + Send + Sync + 'static,
+{
+}
+
+impl<S> Observable for Tracked<S>
+where
+    S: Observable + Clone + Send + Sync + 'static,
+    S::Item: Clone + Send + Sync + 'static,
+{
+    fn signal_ref<B, F>(&self, f: F) -> impl Signal<Item = B> + use<S, B, F>
+    where
+        F: FnMut(&Self::Item) -> B,
+    {
+        self.slot.signal_ref(f)
+    }
+}
@@ -0,0 +1,143 @@
+//! # Lenses
+//!
+//! A [`Lens`] is a [`Slot`] that looks into a part of another slot's value,
+//! so that a single `Mutable<Person>` can be edited field by field.
+//!
+//! ```rust,ignore
+//! struct Person {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! let person = Mutable::new(Person { name: "Ana".into(), age: 27 });
+//!
+//! let name = lens!(person.clone(), name);
+//! name.put("Beatriz".into());
+//! assert_eq!(person.lock_ref().name, "Beatriz");
+//! ```
+//!
+//! Changing the lens changes the whole value,
+//! so everything observing `person` is told about it.
+
+use crate::{Observable, Slot, State};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use futures_signals::signal::Signal;
+
+/// A view of a part of a [`Slot`]'s value, see the [module docs](self).
+///
+/// `get` and `get_mut` must focus onto the same part of the value.
+pub struct Lens<S, T, G, M> {
+    source: S,
+    get: G,
+    get_mut: M,
+    _focus: PhantomData<fn() -> T>,
+}
+
+impl<S, T, G, M> Lens<S, T, G, M>
+where
+    S: Slot,
+    G: Fn(&S::Item) -> &T,
+    M: Fn(&mut S::Item) -> &mut T,
+{
+    pub fn new(source: S, get: G, get_mut: M) -> Self {
+        Self {
+            source,
+            get,
+            get_mut,
+            _focus: PhantomData,
+        }
+    }
+}
+
+impl<S: Clone, T, G: Clone, M: Clone> Clone for Lens<S, T, G, M> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            get: self.get.clone(),
+            get_mut: self.get_mut.clone(),
+            _focus: PhantomData,
+        }
+    }
+}
+
+impl<S, T, G, M> Slot for Lens<S, T, G, M>
+where
+    S: Slot,
+    G: Fn(&S::Item) -> &T,
+    M: Fn(&mut S::Item) -> &mut T,
+{
+    type Item = T;
+
+    fn put(&self, value: Self::Item) {
+        let value = Cell::new(Some(value));
+        self.source.modify(|item| {
+            if let Some(value) = value.take() {
+                *(self.get_mut)(item) = value;
+            }
+        });
+    }
+
+    fn take(&self) -> Self::Item
+    where
+        Self::Item: Copy,
+    {
+        self.with_ref(|value| *value)
+    }
+
+    fn modify<F>(&self, predicate: F)
+    where
+        F: Fn(&mut Self::Item),
+    {
+        self.source.modify(|item| predicate((self.get_mut)(item)));
+    }
+
+    fn with_ref<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&Self::Item) -> R,
+    {
+        self.source.with_ref(|item| f((self.get)(item)))
+    }
+}
+
+impl<S, T, G, M> State for Lens<S, T, G, M>
+where
+    S: Slot,
+    G: Fn(&S::Item) -> &T,
+    M: Fn(&mut S::Item) -> &mut T,
+{
+}
+
+impl<S, T, G, M> Observable for Lens<S, T, G, M>
+where
+    S: Observable,
+    G: Clone + Fn(&S::Item) -> &T,
+    M: Fn(&mut S::Item) -> &mut T,
+{
+    fn signal_ref<B, F>(
+        &self,
+        mut f: F,
+    ) -> impl Signal<Item = B> + use<S, T, G, M, B, F>
+    where
+        F: FnMut(&Self::Item) -> B,
+    {
+        let get = self.get.clone();
+        self.source.signal_ref(move |item| f(get(item)))
+    }
+}
+
+/// Focuses a [`Slot`] onto a field (or a field of a field...) of its value.
+///
+/// ```rust,ignore
+/// let street = lens!(person.clone(), address.street);
+/// ```
+#[macro_export]
+macro_rules! lens {
+    ($slot:expr, $($field:tt).+) => {
+        $crate::Slot::lens(
+            $slot,
+            |it| &it.$($field).+,
+            |it| &mut it.$($field).+,
+        )
+    };
+}
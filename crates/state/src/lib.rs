@@ -12,14 +12,22 @@
 //! That is, UI derived from some state will re-render every time that state changes.
 //! This drives ALL redrawing and layout in UI Composer.
 
+pub mod collections;
 pub mod effect;
+pub mod history;
+pub mod lens;
+#[cfg(feature = "persist")]
+pub mod persist;
 
 /// `futures-signals` reexport.
 pub use futures_signals;
 
 pub mod prelude {
+    pub use crate::collections::SlotAt as _;
     pub use crate::effect::Effect;
-    pub use crate::{Slot, State};
+    pub use crate::history::UndoStack;
+    pub use crate::lens;
+    pub use crate::{Observable, Slot, State};
 
     pub use futures_signals::{map_mut, map_ref};
 
@@ -36,6 +44,9 @@ pub mod prelude {
 }
 
 use crate::effect::Effect;
+use crate::lens::Lens;
+use core::cell::Cell;
+use futures_signals::signal::Signal;
 
 #[diagnostic::on_unimplemented(
     message = "`{Self}` is not a container with interior mutability.",
@@ -52,6 +63,53 @@ pub trait Slot {
     fn modify<F>(&self, predicate: F)
     where
         F: Fn(&mut Self::Item);
+    /// Calls `f` with a reference to the current value, without changing it.
+    ///
+    /// By default, this goes through [`Slot::modify`],
+    /// so slots that can lend their value without changing it should override it.
+    fn with_ref<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&Self::Item) -> R,
+    {
+        let f = Cell::new(Some(f));
+        let result = Cell::new(None);
+        self.modify(|item| {
+            if let Some(f) = f.take() {
+                result.set(Some(f(item)));
+            }
+        });
+        result
+            .into_inner()
+            .expect("`Slot::modify` should call its predicate.")
+    }
+
+    /// Focuses this slot onto a part of its value, see [`Lens`].
+    ///
+    /// For fields, the [`lens!`] macro is shorter.
+    fn lens<T, G, M>(self, get: G, get_mut: M) -> Lens<Self, T, G, M>
+    where
+        Self: Sized,
+        G: Fn(&Self::Item) -> &T,
+        M: Fn(&mut Self::Item) -> &mut T,
+    {
+        Lens::new(self, get, get_mut)
+    }
+}
+
+/// A [`Slot`] whose value can be observed as it changes.
+pub trait Observable: Slot {
+    /// A signal of `f` applied to the current value.
+    fn signal_ref<B, F>(&self, f: F) -> impl Signal<Item = B> + use<Self, B, F>
+    where
+        F: FnMut(&Self::Item) -> B;
+
+    /// A signal of clones of the current value.
+    fn signal_cloned(&self) -> impl Signal<Item = Self::Item> + use<Self>
+    where
+        Self::Item: Clone,
+    {
+        self.signal_ref(Self::Item::clone)
+    }
 }
 
 /// Trait that describes a state which can change.
@@ -70,8 +128,8 @@ pub trait State: Slot {
 
 pub mod implementations {
     use {
-        crate::{Slot, State},
-        futures_signals::signal::Mutable,
+        crate::{Observable, Slot, State},
+        futures_signals::signal::{Mutable, ReadOnlyMutable, Signal},
     };
 
     impl<A> State for Mutable<A> {}
@@ -97,5 +155,21 @@ pub mod implementations {
             let mut lock = self.lock_mut();
             predicate(&mut *lock);
         }
+
+        fn with_ref<R, F>(&self, f: F) -> R
+        where
+            F: FnOnce(&Self::Item) -> R,
+        {
+            f(&self.lock_ref())
+        }
+    }
+
+    impl<A> Observable for Mutable<A> {
+        fn signal_ref<B, F>(&self, f: F) -> impl Signal<Item = B> + use<A, B, F>
+        where
+            F: FnMut(&Self::Item) -> B,
+        {
+            ReadOnlyMutable::signal_ref(self, f)
+        }
     }
 }
@@ -0,0 +1,328 @@
+//! # Persistence
+//!
+//! Binds a [`Mutable`] to a file, so that state survives the app closing.
+//!
+//! ```rust,ignore
+//! #[derive(Serialize, Deserialize, Default)]
+//! struct Settings {
+//!     volume: f32,
+//!     theme: Theme,
+//! }
+//!
+//! let settings = Persisted::new(Mutable::new(Settings::default()), "settings.toml")
+//!     .with_version(2)
+//!     .with_migration(1, |old: SettingsV1| Settings { volume: old.volume, theme: Theme::Dark });
+//!
+//! settings.load()?;
+//! let state = settings.state().clone();
+//! spawn(settings.autosave());
+//! ```
+//!
+//! Files hold the value along with the version of its schema.
+//! When a file from an older version is loaded, it's upgraded with the matching migration.
+//!
+//! The file format is picked from the extension of the path (`.json`, `.toml` or `.ron`),
+//! each one behind a cargo feature of the same name.
+
+use futures::StreamExt as _;
+use futures::future::{self, Either};
+use futures_signals::signal::{Mutable, SignalExt as _};
+use futures_time::{task, time::Duration};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::sync::Mutex;
+
+/// `serde` reexport.
+pub use serde;
+
+/// A file format that state can be saved in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "toml")]
+    Toml,
+    #[cfg(feature = "ron")]
+    Ron,
+}
+
+impl Format {
+    /// The format for files with the extension of `path`, if it's supported.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            #[cfg(feature = "json")]
+            "json" => Some(Format::Json),
+            #[cfg(feature = "toml")]
+            "toml" => Some(Format::Toml),
+            #[cfg(feature = "ron")]
+            "ron" => Some(Format::Ron),
+            _ => None,
+        }
+    }
+
+    pub fn serialize<T: Serialize>(
+        self,
+        value: &T,
+    ) -> Result<String, PersistError> {
+        match self {
+            #[cfg(feature = "json")]
+            Format::Json => serde_json::to_string_pretty(value)
+                .map_err(PersistError::format),
+            #[cfg(feature = "toml")]
+            Format::Toml => {
+                toml::to_string_pretty(value).map_err(PersistError::format)
+            }
+            #[cfg(feature = "ron")]
+            Format::Ron => ron::ser::to_string_pretty(
+                value,
+                ron::ser::PrettyConfig::default(),
+            )
+            .map_err(PersistError::format),
+        }
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(
+        self,
+        text: &str,
+    ) -> Result<T, PersistError> {
+        match self {
+            #[cfg(feature = "json")]
+            Format::Json => {
+                serde_json::from_str(text).map_err(PersistError::format)
+            }
+            #[cfg(feature = "toml")]
+            Format::Toml => toml::from_str(text).map_err(PersistError::format),
+            #[cfg(feature = "ron")]
+            Format::Ron => ron::from_str(text).map_err(PersistError::format),
+        }
+    }
+}
+
+/// Something that went wrong while loading or saving state.
+#[derive(Debug)]
+pub enum PersistError {
+    Io(std::io::Error),
+    /// The file couldn't be parsed, or the value couldn't be written.
+    Format(Box<dyn std::error::Error + Send + Sync>),
+    /// The path doesn't have the extension of any (enabled) [`Format`].
+    UnknownFormat(PathBuf),
+    /// The file is from a version that has no migration.
+    UnknownVersion(u32),
+}
+
+impl PersistError {
+    fn format(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        PersistError::Format(Box::new(error))
+    }
+}
+
+impl Display for PersistError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersistError::Io(error) => error.fmt(f),
+            PersistError::Format(error) => error.fmt(f),
+            PersistError::UnknownFormat(path) => {
+                write!(f, "no known format for {}", path.display())
+            }
+            PersistError::UnknownVersion(version) => {
+                write!(f, "no migration from version {version}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PersistError {}
+
+impl From<std::io::Error> for PersistError {
+    fn from(error: std::io::Error) -> Self {
+        PersistError::Io(error)
+    }
+}
+
+/// What's actually written to files.
+#[derive(Serialize, Deserialize)]
+struct Envelope<T> {
+    version: u32,
+    value: T,
+}
+
+/// Just the version of an [`Envelope`], to know how to read the rest of it.
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+type Migration<T> =
+    Box<dyn Fn(Format, &str) -> Result<T, PersistError> + Send + Sync>;
+
+/// A [`Mutable`] bound to a file, see the [module docs](self).
+pub struct Persisted<T> {
+    state: Mutable<T>,
+    path: PathBuf,
+    format: Option<Format>,
+    version: u32,
+    migrations: BTreeMap<u32, Migration<T>>,
+    debounce: Duration,
+    /// What the file holds, as far as we know, so that autosaving
+    /// doesn't write it again when nothing changed.
+    saved: Mutex<Option<String>>,
+}
+
+impl<T> Persisted<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    pub fn new(state: Mutable<T>, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            state,
+            format: Format::from_path(&path),
+            path,
+            version: 0,
+            migrations: BTreeMap::new(),
+            debounce: Duration::from_millis(500),
+            saved: Mutex::new(None),
+        }
+    }
+
+    /// Uses `format`, regardless of the extension of the path.
+    pub fn with_format(self, format: Format) -> Self {
+        Self {
+            format: Some(format),
+            ..self
+        }
+    }
+
+    /// The version of the schema of `T`, which is saved along with the value.
+    pub fn with_version(self, version: u32) -> Self {
+        Self { version, ..self }
+    }
+
+    /// Upgrades files from `version`, whose values are `Old`s, with `migrate`.
+    pub fn with_migration<Old, F>(mut self, version: u32, migrate: F) -> Self
+    where
+        T: 'static,
+        Old: DeserializeOwned + 'static,
+        F: Fn(Old) -> T + Send + Sync + 'static,
+    {
+        self.migrations.insert(
+            version,
+            Box::new(move |format: Format, text: &str| {
+                let old: Envelope<Old> = format.deserialize(text)?;
+                Ok(migrate(old.value))
+            }),
+        );
+        self
+    }
+
+    /// How long [autosaving](Self::autosave) waits for changes to stop before saving.
+    pub fn with_debounce(self, debounce: Duration) -> Self {
+        Self { debounce, ..self }
+    }
+
+    /// The state bound to the file.
+    pub fn state(&self) -> &Mutable<T> {
+        &self.state
+    }
+
+    /// Replaces the state with what's in the file, returning whether there was a file.
+    ///
+    /// Files from older versions are migrated, then saved again.
+    pub fn load(&self) -> Result<bool, PersistError> {
+        let format = self.format()?;
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(false);
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let Header { version } = format.deserialize(&text)?;
+        if version == self.version {
+            let current: Envelope<T> = format.deserialize(&text)?;
+            self.state.set(current.value);
+            *self.saved.lock().unwrap() = Some(self.serialize()?);
+        } else {
+            let migrate = self
+                .migrations
+                .get(&version)
+                .ok_or(PersistError::UnknownVersion(version))?;
+            self.state.set(migrate(format, &text)?);
+            self.save()?;
+        }
+
+        Ok(true)
+    }
+
+    /// Writes the state to the file.
+    pub fn save(&self) -> Result<(), PersistError> {
+        self.write(self.serialize()?)
+    }
+
+    /// Writes the state to the file, unless the file already holds it.
+    fn save_if_changed(&self) -> Result<(), PersistError> {
+        let text = self.serialize()?;
+        if self.saved.lock().unwrap().as_ref() == Some(&text) {
+            return Ok(());
+        }
+        self.write(text)
+    }
+
+    fn serialize(&self) -> Result<String, PersistError> {
+        self.format()?.serialize(&Envelope {
+            version: self.version,
+            value: &*self.state.lock_ref(),
+        })
+    }
+
+    fn write(&self, text: String) -> Result<(), PersistError> {
+        if let Some(parent) = self.path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Written to the side first, so that a crash can't leave half a file.
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        std::fs::write(&temporary, &text)?;
+        std::fs::rename(&temporary, &self.path)?;
+        *self.saved.lock().unwrap() = Some(text);
+        Ok(())
+    }
+
+    /// Saves the state every time it changes, once changes stop coming
+    /// for a [while](Self::with_debounce).
+    ///
+    /// Changes made before the future is first polled are saved too,
+    /// but the file isn't written again if it already holds the state.
+    ///
+    /// The future stops at the first error.
+    pub async fn autosave(self) -> Result<(), PersistError> {
+        let mut changes = pin!(self.state.signal_ref(|_| ()).to_stream());
+
+        while changes.next().await.is_some() {
+            loop {
+                let quiet = pin!(task::sleep(self.debounce));
+                match future::select(changes.next(), quiet).await {
+                    Either::Left((Some(()), _)) => continue,
+                    Either::Left((None, _)) | Either::Right(_) => break,
+                }
+            }
+
+            self.save_if_changed()?;
+        }
+
+        Ok(())
+    }
+
+    fn format(&self) -> Result<Format, PersistError> {
+        self.format
+            .ok_or_else(|| PersistError::UnknownFormat(self.path.clone()))
+    }
+}
@@ -0,0 +1,28 @@
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use ui_composer_state::effect::animation::{
+    Animation as _, clock::ManualClock, futures_time::time::Duration, lerp,
+};
+use ui_composer_state::futures_signals::signal::Mutable;
+
+#[test]
+fn animations_can_be_stepped_frame_by_frame() {
+    let clock = ManualClock::new();
+    let value = Mutable::new(0.0f32);
+    let mut animation = pin!(
+        lerp(10.0, Duration::from_secs(1))
+            .animate_value_with(clock.clone(), value.clone())
+    );
+    let mut cx = Context::from_waker(Waker::noop());
+
+    assert_eq!(animation.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(value.get(), 0.0);
+
+    clock.advance(Duration::from_millis(500));
+    assert_eq!(animation.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(value.get(), 5.0);
+
+    clock.advance(Duration::from_millis(500));
+    assert_eq!(animation.as_mut().poll(&mut cx), Poll::Ready(()));
+    assert_eq!(value.get(), 10.0);
+}
@@ -0,0 +1,64 @@
+use ui_composer_state::effect::Effect;
+use ui_composer_state::effect::animation::clock::ManualClock;
+use ui_composer_state::effect::animation::futures_time::time::Duration;
+use ui_composer_state::futures_signals::signal::Mutable;
+use ui_composer_state::prelude::{Slot as _, State as _};
+
+#[test]
+fn effects_can_be_sequenced_and_made_conditional() {
+    let log = Mutable::new(Vec::new());
+    let save = log.clone().effect(|log| log.push("save"));
+    let close = log.clone().effect(|log| log.push("close"));
+
+    save.clone().then(close.clone()).apply();
+    (close.clone(), save.clone(), close.clone()).apply();
+    vec![save.clone(), save.clone()].apply();
+    assert_eq!(
+        *log.lock_ref(),
+        ["save", "close", "close", "save", "close", "save", "save"]
+    );
+
+    log.put(Vec::new());
+    let enabled = Mutable::new(false);
+    let mut guarded = save.when(enabled.signal());
+    guarded.apply();
+    enabled.set(true);
+    guarded.apply();
+    assert_eq!(*log.lock_ref(), ["save"]);
+
+    let mut count_and_close =
+        close.map_state(log.clone(), |log| log.push("count"));
+    count_and_close.apply();
+    assert_eq!(*log.lock_ref(), ["save", "count", "close"]);
+}
+
+#[test]
+fn effects_can_be_throttled_and_debounced() {
+    let count = Mutable::new(0);
+    let increment = count.clone().effect(|count| *count += 1);
+
+    let clock = ManualClock::new();
+    let seconds = Duration::from_secs;
+
+    let mut throttled =
+        increment.clone().throttle_with(clock.clone(), seconds(60));
+    throttled.apply();
+    throttled.clone().apply();
+    assert_eq!(count.take(), 1);
+    clock.advance(seconds(60));
+    throttled.apply();
+    assert_eq!(count.take(), 2);
+
+    let mut debounced = increment.debounce_with(clock.clone(), seconds(2));
+    debounced.apply();
+    clock.advance(seconds(1));
+    debounced.apply();
+    clock.advance(seconds(1));
+    // It was applied again a second ago, so it's not due yet.
+    assert_eq!(count.take(), 2);
+
+    clock.advance(seconds(1));
+    assert_eq!(count.take(), 3);
+    clock.advance(seconds(10));
+    assert_eq!(count.take(), 3);
+}
@@ -0,0 +1,86 @@
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll, Waker};
+use ui_composer_state::effect::Effect as _;
+use ui_composer_state::futures_signals::signal::{Mutable, SignalExt as _};
+use ui_composer_state::prelude::{Slot, State as _, UndoStack};
+
+#[test]
+fn changes_can_be_undone_and_redone() {
+    let history = UndoStack::new();
+    let count = history.track(Mutable::new(0));
+    let mut increment = count.clone().effect(|count| *count += 1);
+
+    increment.apply();
+    increment.apply();
+    assert_eq!(count.take(), 2);
+
+    assert!(history.undo());
+    assert_eq!(count.take(), 1);
+    assert!(history.redo());
+    assert_eq!(count.take(), 2);
+    assert!(!history.redo());
+
+    // Transactions are undone all at once.
+    let mut add_two = history.transaction_effect(move || {
+        increment.apply();
+        increment.apply();
+    });
+    add_two.apply();
+    assert_eq!(count.take(), 4);
+
+    let mut undo = history.undo_effect();
+    undo.apply();
+    assert_eq!(count.take(), 2);
+    undo.apply();
+    undo.apply();
+    assert_eq!(count.take(), 0);
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut can_undo = Box::pin(history.can_undo());
+    let mut can_redo = Box::pin(history.can_redo());
+    assert_eq!(
+        can_undo.poll_change_unpin(&mut cx),
+        Poll::Ready(Some(false))
+    );
+    assert_eq!(can_redo.poll_change_unpin(&mut cx), Poll::Ready(Some(true)));
+}
+
+/// A slot that panics while it is fragile, before touching its value.
+#[derive(Clone)]
+struct Fragile(Mutable<i32>);
+
+static FRAGILE: AtomicBool = AtomicBool::new(false);
+
+impl Slot for Fragile {
+    type Item = i32;
+
+    fn put(&self, value: i32) {
+        assert!(!FRAGILE.load(Ordering::Relaxed), "The slot broke.");
+        self.0.put(value);
+    }
+
+    fn take(&self) -> i32 {
+        self.0.take()
+    }
+
+    fn modify<F: Fn(&mut i32)>(&self, predicate: F) {
+        assert!(!FRAGILE.load(Ordering::Relaxed), "The slot broke.");
+        self.0.modify(predicate);
+    }
+}
+
+#[test]
+fn changes_are_recorded_after_an_undo_panics() {
+    let history = UndoStack::new();
+    let count = history.track(Fragile(Mutable::new(0)));
+
+    count.put(1);
+    FRAGILE.store(true, Ordering::Relaxed);
+    assert!(catch_unwind(AssertUnwindSafe(|| history.undo())).is_err());
+    FRAGILE.store(false, Ordering::Relaxed);
+
+    count.put(2);
+    assert!(history.undo());
+    assert_eq!(count.take(), 1);
+}
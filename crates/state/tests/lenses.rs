@@ -0,0 +1,78 @@
+use std::task::{Context, Poll, Waker};
+use ui_composer_state::futures_signals::signal::{Mutable, SignalExt as _};
+use ui_composer_state::futures_signals::signal_map::MutableBTreeMap;
+use ui_composer_state::futures_signals::signal_vec::MutableVec;
+use ui_composer_state::prelude::{Observable, Slot};
+use ui_composer_state::{collections::SlotAt as _, lens};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[test]
+fn lenses_edit_a_field_of_a_mutable() {
+    let person = Mutable::new(Person {
+        name: "Ana".into(),
+        age: 27,
+    });
+    let name = lens!(person.clone(), name);
+    let age = lens!(person.clone(), age);
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut names = Box::pin(name.signal_cloned());
+    assert_eq!(
+        names.poll_change_unpin(&mut cx),
+        Poll::Ready(Some("Ana".into()))
+    );
+
+    name.put("Beatriz".into());
+    age.modify(|age| *age += 1);
+    assert_eq!(
+        person.get_cloned(),
+        Person {
+            name: "Beatriz".into(),
+            age: 28
+        }
+    );
+    assert_eq!(age.take(), 28);
+    assert_eq!(
+        names.poll_change_unpin(&mut cx),
+        Poll::Ready(Some("Beatriz".into()))
+    );
+}
+
+#[test]
+fn collections_have_a_slot_for_each_item() {
+    let numbers = MutableVec::new_with_values(vec![1, 2, 3]);
+    let second = numbers.slot_at(1);
+    second.modify(|n| *n = n.map(|n| n * 10));
+    assert_eq!(second.take(), Some(20));
+    assert_eq!(numbers.lock_ref().to_vec(), [1, 20, 3]);
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut seconds = Box::pin(second.signal_cloned());
+    assert_eq!(
+        seconds.poll_change_unpin(&mut cx),
+        Poll::Ready(Some(Some(20)))
+    );
+
+    // Shrinking the vec empties the slot, instead of panicking.
+    numbers.lock_mut().truncate(1);
+    assert_eq!(seconds.poll_change_unpin(&mut cx), Poll::Ready(Some(None)));
+    assert_eq!(second.take(), None);
+
+    second.put(Some(2));
+    assert_eq!(numbers.lock_ref().to_vec(), [1, 2]);
+
+    let ages = MutableBTreeMap::<&str, u32>::new();
+    let ana = ages.slot_at("Ana");
+    assert_eq!(ana.take(), None);
+
+    ana.put(Some(27));
+    assert_eq!(ages.lock_ref().get("Ana"), Some(&27));
+
+    ana.put(None);
+    assert!(ages.lock_ref().is_empty());
+}
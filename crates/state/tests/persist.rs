@@ -0,0 +1,137 @@
+use futures::future::{self, Either};
+use std::pin::pin;
+use ui_composer_state::effect::animation::futures_time::{
+    task, time::Duration,
+};
+use ui_composer_state::futures_signals::signal::Mutable;
+use ui_composer_state::persist::{
+    Persisted,
+    serde::{Deserialize, Serialize},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "ui_composer_state::persist::serde")]
+struct Settings {
+    volume: f32,
+    muted: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "ui_composer_state::persist::serde")]
+struct SettingsV1 {
+    volume: f32,
+}
+
+#[test]
+fn state_is_saved_loaded_and_migrated() {
+    let dir = std::env::temp_dir()
+        .join(format!("ui-composer-persist-{}", std::process::id()));
+    let path = dir.join("settings.json");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let defaults = Settings {
+        volume: 1.0,
+        muted: false,
+    };
+    let persisted = |state| {
+        Persisted::new(state, &path).with_version(2).with_migration(
+            1,
+            |old: SettingsV1| Settings {
+                volume: old.volume,
+                muted: old.volume == 0.0,
+            },
+        )
+    };
+
+    let fresh = persisted(Mutable::new(defaults.clone()));
+    assert!(!fresh.load().unwrap());
+
+    fresh.state().set(Settings {
+        volume: 0.5,
+        muted: true,
+    });
+    fresh.save().unwrap();
+
+    let restored = persisted(Mutable::new(defaults.clone()));
+    assert!(restored.load().unwrap());
+    assert_eq!(restored.state().get_cloned(), fresh.state().get_cloned());
+
+    std::fs::write(&path, r#"{ "version": 1, "value": { "volume": 0.0 } }"#)
+        .unwrap();
+    let migrated = persisted(Mutable::new(defaults));
+    assert!(migrated.load().unwrap());
+    assert_eq!(
+        migrated.state().get_cloned(),
+        Settings {
+            volume: 0.0,
+            muted: true
+        }
+    );
+    assert!(
+        std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("\"version\": 2")
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn autosaving_writes_changes_once_they_stop() {
+    let dir = std::env::temp_dir()
+        .join(format!("ui-composer-autosave-{}", std::process::id()));
+    let path = dir.join("settings.json");
+    let _ = std::fs::remove_dir_all(&dir);
+    let saved = || {
+        let restored = Persisted::new(Mutable::new(None), &path);
+        restored.load().unwrap();
+        restored.state().get_cloned()
+    };
+
+    let persisted = Persisted::new(
+        Mutable::new(Settings {
+            volume: 1.0,
+            muted: false,
+        }),
+        &path,
+    )
+    .with_debounce(Duration::from_millis(10));
+    assert!(!persisted.load().unwrap());
+    let state = persisted.state().clone();
+
+    // Changed before autosaving even started, which must not be lost.
+    state.lock_mut().volume = 0.5;
+
+    let mut autosave = pin!(persisted.autosave());
+    let mut wait = |duration| {
+        futures::executor::block_on(async {
+            match future::select(autosave.as_mut(), pin!(task::sleep(duration)))
+                .await
+            {
+                Either::Left((result, _)) => panic!("{result:?}"),
+                Either::Right(_) => {}
+            }
+        })
+    };
+
+    wait(Duration::from_millis(100));
+    assert_eq!(
+        saved(),
+        Some(Settings {
+            volume: 0.5,
+            muted: false
+        })
+    );
+
+    state.lock_mut().muted = true;
+    wait(Duration::from_millis(100));
+    assert_eq!(
+        saved(),
+        Some(Settings {
+            volume: 0.5,
+            muted: true
+        })
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -11,12 +11,12 @@ fn app() -> impl Ui {
 
     view! {
         column [
-            flex {} [
+            stacked flex {} [
                 item ColorBox {color: Srgba::new(1.0, 0.0, 0.0, 1.0), size: size} ()
                 item {grow: 1.0} linewise_flow ((MonospaceText(t.clone(), Srgba::new(1.0, 1.0, 0.0, 1.0))))
                 item ColorBox {color: Srgba::new(0.0, 0.0, 1.0, 1.0), size: size} ()
             ]
-            center flex {} [
+            stacked center flex {} [
                 item ColorBox {color: Srgba::new(1.0, 1.0, 0.0, 1.0), size: size} ()
                 item {grow: 1.0} linewise_flow ((MonospaceText(t.clone(), Srgba::new(1.0, 0.0, 1.0, 1.0))))
                 item ColorBox {color: Srgba::new(0.0, 1.0, 0.0, 1.0), size: size} ()
@@ -12,10 +12,9 @@ fn TestingFuture() -> impl Ui {
         let fut = chttp::get_async(
             "https://baconipsum.com/api/?type=meat-and-filler&paras=1&format=text",
         )
-        .then(|res| async {
-            res.expect("Bacon ipsum failed :-(")
-                .text()
-                .expect("Failed to parse response as text.")
+        .map(|res| {
+            let mut res = res.map_err(|e| e.to_string())?;
+            res.text().map_err(|e| e.to_string())
         });
 
         (
@@ -23,12 +22,21 @@ fn TestingFuture() -> impl Ui {
                 rect: hx.rect,
                 color: Srgba::new(1.0, 1.0, 1.0, 1.0),
             },
-            fut.map(move |text| Text {
-                rect: hx.rect.inflate(Size2::new(-1.0, 0.0)),
-                text,
-                color: Srgba::new(1.0, 0.0, 0.0, 1.0),
-            })
-            .into_blueprint(),
+            ErrorBoundary::new(
+                fut.map(move |text| {
+                    text.map(|text| Text {
+                        rect: hx.rect.inflate(Size2::new(-1.0, 0.0)),
+                        text,
+                        color: Srgba::new(1.0, 0.0, 0.0, 1.0),
+                    })
+                })
+                .into_try_blueprint(),
+                Text {
+                    rect: hx.rect.inflate(Size2::new(-1.0, 0.0)),
+                    text: "Bacon ipsum failed :-(".into(),
+                    color: Srgba::new(1.0, 0.0, 0.0, 1.0),
+                },
+            ),
         )
     })
     .with_minimum_size(Size2::new(64.0, 16.0))
@@ -31,8 +31,8 @@ fn ReactiveLabel(
         let text = text_signal
             .signal_ref(move |text| {
                 let mut l = Label(text.clone());
-                l.prepare(hx);
-                l.place(hx)
+                l.prepare(hx.clone());
+                l.place(hx.clone())
             })
             .into_blueprint();
 